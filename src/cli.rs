@@ -0,0 +1,374 @@
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono_tz::Tz;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use eframe::egui;
+use reminder_display::{
+    CronSchedule, Priority, Recurrence, Reminder, RotationKind, Theme, TimeWindows, Weekday,
+};
+use std::path::PathBuf;
+
+/// Fullscreen display that rotates through work reminders.
+///
+/// Options can also be set through the environment variables shown; options
+/// on the command line take precedence. The interval, rotation, timezone,
+/// theme and snooze lengths can also be set in the reminders file's settings,
+/// which apply where neither does. Without a command, the display opens.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Reminders file, or a directory of them [default: work_reminders.json
+    /// in the current or home directory]
+    #[arg(short, long, env = "REMINDERS_FILE", value_name = "PATH")]
+    pub file: Option<PathBuf>,
+
+    /// Seconds each reminder stays on screen, unless it sets duration_secs
+    /// [default: 30]
+    #[arg(
+        short,
+        long,
+        value_name = "SECS",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub interval: Option<u64>,
+
+    /// How to pick the next reminder: round-robin, priority-weighted, shuffle
+    /// or pin-urgent [default: round-robin]
+    #[arg(long, env = "REMINDERS_ROTATION", value_name = "STRATEGY")]
+    pub rotation: Option<RotationKind>,
+
+    /// Zone for reminders without their own timezone, e.g. Europe/Berlin
+    /// [default: local time]
+    #[arg(long, env = "REMINDERS_TIMEZONE", value_name = "ZONE")]
+    pub timezone: Option<Tz>,
+
+    /// Holiday calendar (.ics or .json) [default: holidays.ics or
+    /// holidays.json next to the reminders file]
+    #[arg(long, env = "REMINDERS_HOLIDAYS", value_name = "PATH")]
+    pub holidays: Option<PathBuf>,
+
+    /// Snooze lengths offered on screen, in minutes [default: 5,15,60]
+    #[arg(
+        long,
+        env = "REMINDERS_SNOOZE_MINUTES",
+        value_name = "MINUTES",
+        value_delimiter = ',',
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub snooze_minutes: Option<Vec<u32>>,
+
+    /// Run in a normal window instead of fullscreen
+    #[arg(short, long)]
+    pub windowed: bool,
+
+    /// Window width, in points
+    #[arg(long, value_name = "POINTS", default_value_t = 800.0)]
+    pub width: f32,
+
+    /// Window height, in points
+    #[arg(long, value_name = "POINTS", default_value_t = 480.0)]
+    pub height: f32,
+
+    /// Open on the monitor containing this desktop position, e.g. 1920,0 for
+    /// a display to the right of a 1920-wide primary one
+    #[arg(long, value_name = "X,Y", value_parser = parse_position)]
+    pub monitor: Option<egui::Pos2>,
+
+    /// Color theme: dark, light, or system to follow the desktop
+    /// [default: system]
+    #[arg(long, value_name = "THEME")]
+    pub theme: Option<Theme>,
+
+    /// Least severe messages written to stderr: off, error, warn, info,
+    /// debug or trace
+    #[arg(long, value_name = "LEVEL", default_value = "warn")]
+    pub log_level: log::LevelFilter,
+}
+
+impl Cli {
+    /// Checks what clap can't express in attributes.
+    pub fn validate(&self) -> Result<(), String> {
+        // One number key per snooze length
+        if let Some(snooze_minutes) = &self.snooze_minutes
+            && snooze_minutes.len() > crate::SNOOZE_KEYS.len()
+        {
+            return Err(format!(
+                "at most {} snooze lengths can be given",
+                crate::SNOOZE_KEYS.len()
+            ));
+        }
+        if !(self.width > 0.0 && self.height > 0.0) {
+            return Err("window width and height must be positive".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn viewport(&self) -> egui::ViewportBuilder {
+        let mut viewport = egui::ViewportBuilder::default()
+            .with_title("Work Reminders")
+            .with_inner_size([self.width, self.height]);
+        if let Some(position) = self.monitor {
+            viewport = viewport.with_position(position);
+        }
+
+        if self.windowed {
+            viewport
+        } else {
+            // Fullscreen goes to whichever monitor the window opens on
+            viewport
+                .with_fullscreen(true)
+                .with_always_on_top()
+                .with_decorations(false)
+                .with_resizable(false)
+        }
+    }
+}
+
+/// Changes to the reminders file, and checks of it, run from the terminal.
+/// A running display picks up changes through its reload.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// List every reminder in the file with its ID and schedule
+    List,
+    /// Add a reminder
+    Add(AddArgs),
+    /// Change fields of a reminder
+    Edit(EditArgs),
+    /// Remove a reminder
+    Remove {
+        /// ID of the reminder, as shown by list
+        id: String,
+    },
+    /// Check reminders files for errors without opening the display, e.g. in
+    /// a pre-commit hook. Exits with status 1 if any file has problems.
+    ///
+    /// Besides everything that stops the display loading a file, this rejects
+    /// unknown fields and reminders that appear more than once.
+    Validate {
+        /// Files, or directories of them, to check [default: the reminders
+        /// file]
+        #[arg(value_name = "PATH")]
+        files: Vec<PathBuf>,
+    },
+    /// Rewrite a reminders file from before settings (a bare list of
+    /// reminders) as a version 2 document, keeping its comments
+    Migrate {
+        /// File, or directory of them, to migrate [default: the reminders
+        /// file]
+        #[arg(value_name = "PATH")]
+        file: Option<PathBuf>,
+    },
+    /// Show which reminders the display would rotate through
+    Preview {
+        /// Time to preview, as "YYYY-MM-DD HH:MM" or "HH:MM" for today
+        /// [default: now]
+        #[arg(long, value_name = "TIME", value_parser = parse_time)]
+        at: Option<NaiveDateTime>,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct AddArgs {
+    /// Text shown on screen
+    pub text: String,
+
+    /// Stable ID to edit or remove it by [default: derived from the content]
+    #[arg(long)]
+    pub id: Option<String>,
+
+    #[arg(short, long)]
+    pub category: String,
+
+    /// low, medium, high or urgent
+    #[arg(short, long, default_value_t = Priority::Medium)]
+    pub priority: Priority,
+
+    #[command(flatten)]
+    pub schedule: ScheduleArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct EditArgs {
+    /// ID of the reminder, as shown by list
+    pub id: String,
+
+    /// Text shown on screen
+    #[arg(long)]
+    pub text: Option<String>,
+
+    #[arg(short, long)]
+    pub category: Option<String>,
+
+    /// low, medium, high or urgent
+    #[arg(short, long)]
+    pub priority: Option<Priority>,
+
+    #[command(flatten)]
+    pub schedule: ScheduleArgs,
+
+    /// Clear a field, e.g. --unset time-range
+    #[arg(long, value_enum, value_name = "FIELD")]
+    pub unset: Vec<Field>,
+}
+
+impl EditArgs {
+    /// Clears the `--unset` fields, then sets the given ones.
+    pub fn apply_to(&self, reminder: &mut Reminder) {
+        for field in &self.unset {
+            field.clear(reminder);
+        }
+        if let Some(text) = &self.text {
+            reminder.text = text.clone();
+        }
+        if let Some(category) = &self.category {
+            reminder.category = category.clone();
+        }
+        if let Some(priority) = self.priority {
+            reminder.priority = priority;
+        }
+        self.schedule.apply_to(reminder);
+    }
+}
+
+/// When a reminder shows. Options left out leave the field as it is.
+#[derive(Debug, Args)]
+pub struct ScheduleArgs {
+    /// Times of day, e.g. "09:00-17:00", "morning, 16:00-17:00", or "lunch"
+    /// for a period defined in the file's settings
+    #[arg(long, value_name = "WINDOWS")]
+    pub time_range: Option<TimeWindows>,
+
+    /// Days of the week, e.g. mon,wed,fri
+    #[arg(long, value_name = "DAYS", value_delimiter = ',')]
+    pub days: Option<Vec<Weekday>>,
+
+    /// The only day it shows, e.g. 2026-11-03
+    #[arg(long, value_name = "DATE")]
+    pub date: Option<NaiveDate>,
+
+    /// First day it shows
+    #[arg(long, value_name = "DATE")]
+    pub start_date: Option<NaiveDate>,
+
+    /// Last day it shows
+    #[arg(long, value_name = "DATE")]
+    pub end_date: Option<NaiveDate>,
+
+    /// iCalendar rule, e.g. "DTSTART:20260106 RRULE:FREQ=MONTHLY;BYDAY=-1FR"
+    #[arg(long, value_name = "RULE")]
+    pub recurrence: Option<Recurrence>,
+
+    /// Cron expression, instead of a time range and days, e.g. "0 9 * * 1-5"
+    #[arg(long, value_name = "EXPR")]
+    pub cron: Option<CronSchedule>,
+
+    /// Zone its times are in, e.g. Europe/Berlin
+    #[arg(long, value_name = "ZONE")]
+    pub timezone: Option<Tz>,
+
+    /// Hide it on holidays
+    #[arg(long)]
+    pub skip_holidays: bool,
+
+    /// Show it only on holidays
+    #[arg(long)]
+    pub only_on_holidays: bool,
+
+    /// Seconds on screen, instead of the rotation interval
+    #[arg(long, value_name = "SECS")]
+    pub duration_secs: Option<u64>,
+}
+
+impl ScheduleArgs {
+    pub fn apply_to(&self, reminder: &mut Reminder) {
+        if let Some(time_range) = &self.time_range {
+            reminder.time_range = Some(time_range.clone());
+        }
+        if let Some(days) = &self.days {
+            reminder.days = Some(days.clone());
+        }
+        if let Some(date) = self.date {
+            reminder.date = Some(date);
+        }
+        if let Some(start_date) = self.start_date {
+            reminder.start_date = Some(start_date);
+        }
+        if let Some(end_date) = self.end_date {
+            reminder.end_date = Some(end_date);
+        }
+        if let Some(recurrence) = &self.recurrence {
+            reminder.recurrence = Some(recurrence.clone());
+        }
+        if let Some(cron) = &self.cron {
+            reminder.cron = Some(cron.clone());
+        }
+        if let Some(timezone) = self.timezone {
+            reminder.timezone = Some(timezone);
+        }
+        reminder.skip_holidays |= self.skip_holidays;
+        reminder.only_on_holidays |= self.only_on_holidays;
+        if let Some(duration_secs) = self.duration_secs {
+            reminder.duration_secs = Some(duration_secs);
+        }
+    }
+}
+
+/// Reminder fields that `edit --unset` can clear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Field {
+    TimeRange,
+    Days,
+    Date,
+    StartDate,
+    EndDate,
+    Recurrence,
+    Cron,
+    Timezone,
+    SkipHolidays,
+    OnlyOnHolidays,
+    DurationSecs,
+}
+
+impl Field {
+    fn clear(&self, reminder: &mut Reminder) {
+        match self {
+            Field::TimeRange => reminder.time_range = None,
+            Field::Days => reminder.days = None,
+            Field::Date => reminder.date = None,
+            Field::StartDate => reminder.start_date = None,
+            Field::EndDate => reminder.end_date = None,
+            Field::Recurrence => reminder.recurrence = None,
+            Field::Cron => reminder.cron = None,
+            Field::Timezone => reminder.timezone = None,
+            Field::SkipHolidays => reminder.skip_holidays = false,
+            Field::OnlyOnHolidays => reminder.only_on_holidays = false,
+            Field::DurationSecs => reminder.duration_secs = None,
+        }
+    }
+}
+
+fn parse_position(s: &str) -> Result<egui::Pos2, String> {
+    let (x, y) = s
+        .split_once(',')
+        .ok_or_else(|| format!("expected X,Y, got \"{}\"", s))?;
+    let coordinate = |v: &str| {
+        v.trim()
+            .parse::<f32>()
+            .map_err(|_| format!("\"{}\" is not a coordinate", v.trim()))
+    };
+    Ok(egui::pos2(coordinate(x)?, coordinate(y)?))
+}
+
+fn parse_time(s: &str) -> Result<NaiveDateTime, String> {
+    let s = s.trim();
+    for format in ["%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"] {
+        if let Ok(time) = NaiveDateTime::parse_from_str(s, format) {
+            return Ok(time);
+        }
+    }
+    NaiveTime::parse_from_str(s, "%H:%M")
+        .map(|time| Local::now().date_naive().and_time(time))
+        .map_err(|_| format!("expected \"YYYY-MM-DD HH:MM\" or \"HH:MM\", got \"{}\"", s))
+}
@@ -0,0 +1,327 @@
+use chrono::{Local, NaiveDateTime, TimeZone};
+use reminder_display::{
+    CURRENT_VERSION, Clock, ManualClock, Reminder, ReminderError, ReminderManager, SourceFile,
+    SystemClock, lint_sources, migrate_document, read_sources,
+};
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use crate::cli::{AddArgs, Cli, Command, EditArgs};
+
+/// Runs a command against the reminders file, without opening the display.
+pub fn run(command: &Command, cli: &Cli) -> ExitCode {
+    let result = match command {
+        Command::List => list(cli),
+        Command::Add(args) => add(cli, args),
+        Command::Edit(args) => edit(cli, args),
+        Command::Remove { id } => remove(cli, id),
+        Command::Validate { files } => validate(cli, files),
+        Command::Migrate { file } => migrate(cli, file.as_ref()),
+        Command::Preview { at } => preview(cli, *at),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn list(cli: &Cli) -> Result<(), String> {
+    let manager = load_manager(cli, Arc::new(SystemClock))?;
+    output(write_list(&mut io::stdout().lock(), &manager))
+}
+
+fn write_list(out: &mut impl Write, manager: &ReminderManager) -> io::Result<()> {
+    // Which file each reminder is in, when they come from several
+    let several = manager.source_paths().len() > 1;
+    for reminder in manager.list() {
+        writeln!(
+            out,
+            "{}  [{}] {}: {}",
+            reminder.id(),
+            reminder.priority,
+            reminder.category,
+            reminder.text
+        )?;
+        match reminder.source.as_deref().and_then(|path| path.file_name()) {
+            Some(file_name) if several => writeln!(
+                out,
+                "    {}  ({})",
+                describe_schedule(reminder),
+                file_name.to_string_lossy()
+            )?,
+            _ => writeln!(out, "    {}", describe_schedule(reminder))?,
+        }
+    }
+    Ok(())
+}
+
+// Output cut short by a closed pipe, as with `| head`, isn't a failure
+fn output(result: io::Result<()>) -> Result<(), String> {
+    match result {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result.map_err(|e| format!("can't write the output: {}", e)),
+    }
+}
+
+fn add(cli: &Cli, args: &AddArgs) -> Result<(), String> {
+    let mut manager = load_manager(cli, Arc::new(SystemClock))?;
+    let mut reminder = Reminder {
+        id: args.id.clone(),
+        text: args.text.clone(),
+        category: args.category.clone(),
+        priority: args.priority,
+        ..Default::default()
+    };
+    args.schedule.apply_to(&mut reminder);
+
+    // upsert would replace it, which isn't what adding means
+    let id = reminder.id();
+    if manager.get(&id).is_some() {
+        return Err(match &reminder.id {
+            Some(_) => format!("a reminder with ID \"{}\" already exists", id),
+            None => "the same reminder already exists".to_string(),
+        });
+    }
+
+    manager.upsert(reminder).map_err(|e| report(&e))?;
+    println!("Added reminder {}", id);
+    Ok(())
+}
+
+fn edit(cli: &Cli, args: &EditArgs) -> Result<(), String> {
+    let mut manager = load_manager(cli, Arc::new(SystemClock))?;
+    let Some(original) = manager.get(&args.id) else {
+        return Err(no_such_reminder(&manager, &args.id));
+    };
+
+    let mut reminder = original.clone();
+    args.apply_to(&mut reminder);
+
+    // A derived ID follows the content
+    let id = reminder.id();
+    manager
+        .replace(&args.id, reminder)
+        .map_err(|e| report(&e))?;
+    match id == args.id {
+        true => println!("Updated reminder {}", id),
+        false => println!("Updated reminder {}, now {}", args.id, id),
+    }
+    Ok(())
+}
+
+fn remove(cli: &Cli, id: &str) -> Result<(), String> {
+    let mut manager = load_manager(cli, Arc::new(SystemClock))?;
+    match manager.remove(id).map_err(|e| report(&e))? {
+        Some(removed) => {
+            println!("Removed reminder {} (\"{}\")", id, removed.text);
+            Ok(())
+        }
+        None => Err(no_such_reminder(&manager, id)),
+    }
+}
+
+fn validate(cli: &Cli, paths: &[PathBuf]) -> Result<(), String> {
+    let paths = match paths {
+        [] => vec![reminders_path(cli)],
+        paths => paths.to_vec(),
+    };
+
+    let (mut checked, mut failed) = (0, 0);
+    for path in &paths {
+        // A directory's files, and included ones, are checked together, as
+        // the display loads them
+        let results = match read_sources(path) {
+            Ok(files) => files
+                .iter()
+                .map(|file| file.path.clone())
+                .zip(lint_sources(&files))
+                .collect(),
+            Err(error) => vec![(path.clone(), Err(error))],
+        };
+        for (path, result) in results {
+            checked += 1;
+            match result {
+                Ok(reminders) => println!(
+                    "{}: {} reminders, no problems found",
+                    path.display(),
+                    reminders.len()
+                ),
+                Err(error) => {
+                    failed += 1;
+                    // One line per problem, each with its file, for editors and hooks
+                    match error.issues() {
+                        [] => eprintln!("{}", error),
+                        issues => {
+                            for issue in issues {
+                                eprintln!("{}: {}", path.display(), issue);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!(
+            "{} of {} {} problems",
+            failed,
+            checked,
+            match (checked, failed) {
+                (1, _) => "file has",
+                (_, 1) => "files has",
+                _ => "files have",
+            }
+        )),
+    }
+}
+
+fn migrate(cli: &Cli, file: Option<&PathBuf>) -> Result<(), String> {
+    let path = file.cloned().unwrap_or_else(|| reminders_path(cli));
+
+    // Each of a directory's files, and included ones
+    for SourceFile { path, content } in read_sources(&path).map_err(|e| report(&e))? {
+        match migrate_document(&path, &content).map_err(|e| report(&e))? {
+            Some(migrated) => {
+                fs::write(&path, migrated).map_err(|e| report(&ReminderError::io(&path, e)))?;
+                println!("Migrated {} to version {}", path.display(), CURRENT_VERSION);
+            }
+            None => println!("{} is already version {}", path.display(), CURRENT_VERSION),
+        }
+    }
+    Ok(())
+}
+
+// Unlike the display, never creates a missing reminders file, so a command
+// given the wrong path doesn't leave a file of defaults behind. Any failure
+// to load is the command's error.
+fn load_manager(cli: &Cli, clock: Arc<dyn Clock>) -> Result<ReminderManager, String> {
+    let manager = ReminderManager::with_file(reminders_path(cli).to_string_lossy(), clock);
+    match manager.last_error() {
+        Some(error) => Err(report(error)),
+        None => Ok(crate::configure_manager(cli, manager)),
+    }
+}
+
+// --file, or the file the display would find
+fn reminders_path(cli: &Cli) -> PathBuf {
+    cli.file
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(ReminderManager::find_reminders_file()))
+}
+
+fn preview(cli: &Cli, at: Option<NaiveDateTime>) -> Result<(), String> {
+    let clock: Arc<dyn Clock> = match at {
+        Some(at) => Arc::new(ManualClock::new(local_time(cli, at)?)),
+        None => Arc::new(SystemClock),
+    };
+    let manager = load_manager(cli, clock)?;
+    output(write_preview(&mut io::stdout().lock(), cli, &manager))
+}
+
+fn write_preview(out: &mut impl Write, cli: &Cli, manager: &ReminderManager) -> io::Result<()> {
+    let active = manager.active_reminders();
+    writeln!(
+        out,
+        "{}: {} of {} reminders shown, {}",
+        manager.current_time(),
+        active.len(),
+        manager.list().len(),
+        cli.rotation
+            .or(manager.settings().rotation)
+            .unwrap_or_default()
+    )?;
+    for (i, reminder) in active.iter().enumerate() {
+        writeln!(
+            out,
+            "{:>3}. [{}] {}: {} ({}s)",
+            i + 1,
+            reminder.priority,
+            reminder.category,
+            reminder.text,
+            reminder
+                .duration_secs
+                .unwrap_or_else(|| manager.rotation_interval())
+        )?;
+    }
+    Ok(())
+}
+
+// `at` is in the --timezone zone if one is given, otherwise local time
+fn local_time(cli: &Cli, at: NaiveDateTime) -> Result<chrono::DateTime<Local>, String> {
+    let time = match cli.timezone {
+        Some(zone) => zone
+            .from_local_datetime(&at)
+            .earliest()
+            .map(|t| t.with_timezone(&Local)),
+        None => Local.from_local_datetime(&at).earliest(),
+    };
+    time.ok_or_else(|| format!("{} doesn't exist in that time zone", at))
+}
+
+fn describe_schedule(reminder: &Reminder) -> String {
+    let mut parts = Vec::new();
+    if let Some(time_range) = &reminder.time_range {
+        parts.push(time_range.to_string());
+    }
+    if let Some(days) = &reminder.days {
+        let days: Vec<&str> = days.iter().map(|day| day.as_str()).collect();
+        parts.push(days.join(", "));
+    }
+    if let Some(date) = reminder.date {
+        parts.push(format!("on {}", date));
+    }
+    if let Some(start_date) = reminder.start_date {
+        parts.push(format!("from {}", start_date));
+    }
+    if let Some(end_date) = reminder.end_date {
+        parts.push(format!("until {}", end_date));
+    }
+    if let Some(recurrence) = &reminder.recurrence {
+        parts.push(recurrence.to_string());
+    }
+    if let Some(cron) = &reminder.cron {
+        parts.push(format!("cron {}", cron));
+    }
+    if let Some(timezone) = reminder.timezone {
+        parts.push(format!("in {}", timezone));
+    }
+    if reminder.skip_holidays {
+        parts.push("not on holidays".to_string());
+    }
+    if reminder.only_on_holidays {
+        parts.push("only on holidays".to_string());
+    }
+    if let Some(duration_secs) = reminder.duration_secs {
+        parts.push(format!("{}s on screen", duration_secs));
+    }
+
+    if parts.is_empty() {
+        "any time".to_string()
+    } else {
+        parts.join("; ")
+    }
+}
+
+fn no_such_reminder(manager: &ReminderManager, id: &str) -> String {
+    match manager.last_error() {
+        Some(error) => report(error),
+        None => format!("no reminder with ID \"{}\"", id),
+    }
+}
+
+// The error, followed by each problem with an entry on its own line
+fn report(error: &ReminderError) -> String {
+    let mut message = error.to_string();
+    for issue in error.issues() {
+        message.push_str(&format!("\n  {}", issue));
+    }
+    message
+}
@@ -0,0 +1,330 @@
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Timelike,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::schedule::ParseScheduleError;
+
+/// How long a cron reminder stays visible after firing when no duration is
+/// given.
+pub const DEFAULT_CRON_DURATION_MINS: u32 = 15;
+
+/// The longest a cron reminder can stay visible after firing: a day.
+pub const MAX_CRON_DURATION_MINS: u32 = 24 * 60;
+
+/// A standard 5-field cron expression: minute, hour, day of month, month and
+/// day of week.
+///
+/// Fields accept `*`, numbers, ranges (`1-5`), lists (`1,15`) and steps
+/// (`*/15`, `9-17/2`). Months and weekdays also accept three-letter names
+/// (`jan`, `mon`), and both 0 and 7 mean Sunday. As in cron, when both day of
+/// month and day of week are restricted, a day matching either one fires.
+/// The `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` shorthands
+/// are accepted too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpression {
+    source: String,
+    minutes: u64,
+    hours: u32,
+    days_of_month: u32,
+    months: u16,
+    days_of_week: u8,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronExpression {
+    /// Whether the expression fires in the minute of `time`.
+    pub fn matches(&self, time: &NaiveDateTime) -> bool {
+        self.matches_day(time.date())
+            && bit64(self.minutes, time.minute())
+            && bit(self.hours, time.hour())
+    }
+
+    /// The latest minute at or before `time` in which the expression fires,
+    /// if it's no earlier than `earliest`. A month, day or hour that can't
+    /// match is skipped as a whole, so this takes a few steps per hour or day
+    /// searched, not one per minute.
+    pub fn latest_match(
+        &self,
+        time: NaiveDateTime,
+        earliest: NaiveDateTime,
+    ) -> Option<NaiveDateTime> {
+        let mut time = time.with_second(0)?.with_nanosecond(0)?;
+        while time >= earliest {
+            let date = time.date();
+            // Where to look next: the last minute before this month, day or
+            // hour
+            let before = if !self.matches_day(date) {
+                match bit(u32::from(self.months), date.month()) {
+                    true => date,
+                    false => date.with_day(1)?,
+                }
+                .and_hms_opt(0, 0, 0)?
+            } else if !bit(self.hours, time.hour()) {
+                date.and_hms_opt(time.hour(), 0, 0)?
+            } else {
+                // Minutes up to and including this one
+                let earlier = self.minutes & ((2 << time.minute()) - 1);
+                if earlier != 0 {
+                    let found = time.with_minute(63 - earlier.leading_zeros())?;
+                    return (found >= earliest).then_some(found);
+                }
+                date.and_hms_opt(time.hour(), 0, 0)?
+            };
+            time = before - Duration::minutes(1);
+        }
+        None
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day_of_month = bit(self.days_of_month, date.day());
+        let day_of_week = bit(
+            u32::from(self.days_of_week),
+            date.weekday().num_days_from_sunday(),
+        );
+        let day = match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        };
+        day && bit(u32::from(self.months), date.month())
+    }
+}
+
+fn bit(mask: u32, n: u32) -> bool {
+    mask & (1 << n) != 0
+}
+
+fn bit64(mask: u64, n: u32) -> bool {
+    mask & (1 << n) != 0
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+fn error(message: impl Into<String>) -> ParseScheduleError {
+    ParseScheduleError::new(message)
+}
+
+/// Parses one field into a bitmask of the values it selects.
+fn parse_field(
+    field: &str,
+    name: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+    names_start: u32,
+) -> Result<u64, ParseScheduleError> {
+    let value = |s: &str| -> Result<u32, ParseScheduleError> {
+        let lower = s.to_lowercase();
+        if let Some(i) = names.iter().position(|n| *n == lower) {
+            return Ok(names_start + i as u32);
+        }
+        s.parse::<u32>()
+            .ok()
+            .filter(|n| (min..=max).contains(n))
+            .ok_or_else(|| error(format!("invalid {} \"{}\" in cron expression", name, s)))
+    };
+
+    let mut mask = 0u64;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step = step.parse::<u32>().ok().filter(|s| *s > 0).ok_or_else(|| {
+                    error(format!("invalid step \"{}\" in cron expression", step))
+                })?;
+                (range, step)
+            }
+            None => (item, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (value(start)?, value(end)?)
+        } else {
+            let start = value(range)?;
+            // "5/15" means every 15 starting at 5
+            (start, if step > 1 { max } else { start })
+        };
+        if start > end {
+            return Err(error(format!(
+                "invalid {} range \"{}\" in cron expression",
+                name, range
+            )));
+        }
+
+        for n in (start..=end).step_by(step as usize) {
+            mask |= 1 << n;
+        }
+    }
+    Ok(mask)
+}
+
+impl FromStr for CronExpression {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expanded = match s.trim().to_lowercase().as_str() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            _ => s,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(error(format!(
+                "cron expression \"{}\" must have 5 fields (minute hour day-of-month month day-of-week)",
+                s
+            )));
+        };
+
+        let mut days_of_week = parse_field(day_of_week, "day of week", 0, 7, &WEEKDAY_NAMES, 0)?;
+        // 7 is another name for Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(CronExpression {
+            source: s.trim().to_string(),
+            minutes: parse_field(minute, "minute", 0, 59, &[], 0)?,
+            hours: parse_field(hour, "hour", 0, 23, &[], 0)? as u32,
+            days_of_month: parse_field(day_of_month, "day of month", 1, 31, &[], 0)? as u32,
+            months: parse_field(month, "month", 1, 12, &MONTH_NAMES, 1)? as u16,
+            days_of_week: days_of_week as u8,
+            // As in cron, "*/2" still counts as unrestricted
+            day_of_month_restricted: !day_of_month.starts_with('*'),
+            day_of_week_restricted: !day_of_week.starts_with('*'),
+        })
+    }
+}
+
+impl fmt::Display for CronExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// A cron expression plus how long the reminder stays visible after each
+/// time it fires.
+///
+/// Written either as a plain expression (`"0 9 * * 1-5"`, shown for
+/// [`DEFAULT_CRON_DURATION_MINS`]) or as an object
+/// (`{"schedule": "0 9 * * 1-5", "duration_mins": 30}`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    pub expression: CronExpression,
+    pub duration_mins: u32,
+}
+
+impl CronSchedule {
+    /// Times within the last `duration_mins` minutes (including the current
+    /// minute) at which the expression fired, latest wall-clock time first.
+    pub fn recent_fires<'a, Tz: TimeZone + 'a>(
+        &'a self,
+        now: &DateTime<Tz>,
+    ) -> impl Iterator<Item = DateTime<Tz>> + 'a {
+        let latest = now
+            .with_second(0)
+            .and_then(|t| t.with_nanosecond(0))
+            .unwrap_or_else(|| now.clone());
+        let earliest = latest.clone() - Duration::minutes(i64::from(self.duration_mins) - 1);
+
+        // The expression is matched against wall-clock times, which can run
+        // past either end of the window when the clocks change within it
+        let span = latest.clone() - earliest.clone();
+        let low = earliest.naive_local().min(latest.naive_local() - span);
+        let high = latest.naive_local().max(earliest.naive_local() + span);
+
+        let zone = now.timezone();
+        std::iter::successors(self.expression.latest_match(high, low), move |found| {
+            self.expression
+                .latest_match(*found - Duration::minutes(1), low)
+        })
+        .flat_map(move |found| match zone.from_local_datetime(&found) {
+            LocalResult::Single(time) => vec![time],
+            // A minute the clocks went back over happens twice
+            LocalResult::Ambiguous(first, second) => vec![second, first],
+            LocalResult::None => Vec::new(),
+        })
+        .filter(move |time| *time >= earliest && *time <= latest)
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(CronSchedule {
+            expression: s.parse()?,
+            duration_mins: DEFAULT_CRON_DURATION_MINS,
+        })
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (for {} min)", self.expression, self.duration_mins)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum RawCronSchedule {
+    Expression(String),
+    Detailed {
+        schedule: String,
+        #[serde(default)]
+        duration_mins: Option<u32>,
+    },
+}
+
+impl Serialize for CronSchedule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let raw = if self.duration_mins == DEFAULT_CRON_DURATION_MINS {
+            RawCronSchedule::Expression(self.expression.to_string())
+        } else {
+            RawCronSchedule::Detailed {
+                schedule: self.expression.to_string(),
+                duration_mins: Some(self.duration_mins),
+            }
+        };
+        raw.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CronSchedule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (schedule, duration_mins) = match RawCronSchedule::deserialize(deserializer)? {
+            RawCronSchedule::Expression(schedule) => (schedule, None),
+            RawCronSchedule::Detailed {
+                schedule,
+                duration_mins,
+            } => (schedule, duration_mins),
+        };
+
+        let duration_mins = duration_mins.unwrap_or(DEFAULT_CRON_DURATION_MINS);
+        if duration_mins == 0 {
+            return Err(serde::de::Error::custom(
+                "cron duration_mins must be at least 1",
+            ));
+        }
+        if duration_mins > MAX_CRON_DURATION_MINS {
+            return Err(serde::de::Error::custom(format!(
+                "cron duration_mins must be at most {} (a day)",
+                MAX_CRON_DURATION_MINS
+            )));
+        }
+        Ok(CronSchedule {
+            expression: schedule.parse().map_err(serde::de::Error::custom)?,
+            duration_mins,
+        })
+    }
+}
@@ -0,0 +1,83 @@
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize};
+
+use crate::reminders::Reminder;
+use crate::settings::Settings;
+
+/// The version written by this release. Version 1 is the original layout, a
+/// bare array of reminders with no settings.
+pub const CURRENT_VERSION: u32 = 2;
+
+/// The whole content of a reminders file: its settings and reminders.
+///
+/// Files at [`CURRENT_VERSION`] are an object (or TOML table) with
+/// `version`, `settings` and `reminders`; version 1 files are read as one with
+/// default settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReminderDocument<E = Reminder> {
+    #[serde(deserialize_with = "supported_version")]
+    pub version: u32,
+    /// `false` leaves the file's reminders and settings out when it's one
+    /// of several, such as in a reminders directory.
+    #[serde(default = "enabled", skip_serializing_if = "is_enabled")]
+    pub enabled: bool,
+    /// Other files to read before this one, as paths or glob patterns
+    /// relative to this file's directory. See [`crate::read_sources`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    #[serde(default, skip_serializing_if = "Settings::is_empty")]
+    pub settings: Settings,
+    #[serde(default = "Vec::new")]
+    pub reminders: Vec<E>,
+}
+
+impl<E> ReminderDocument<E> {
+    /// A document at [`CURRENT_VERSION`].
+    pub fn new(settings: Settings, reminders: Vec<E>) -> Self {
+        ReminderDocument {
+            version: CURRENT_VERSION,
+            enabled: true,
+            include: Vec::new(),
+            settings,
+            reminders,
+        }
+    }
+
+    /// A version 1 file: just the reminders.
+    pub fn legacy(reminders: Vec<E>) -> Self {
+        ReminderDocument {
+            version: 1,
+            enabled: true,
+            include: Vec::new(),
+            settings: Settings::default(),
+            reminders,
+        }
+    }
+
+    pub fn is_legacy(&self) -> bool {
+        self.version < CURRENT_VERSION
+    }
+}
+
+fn enabled() -> bool {
+    true
+}
+
+fn is_enabled(enabled: &bool) -> bool {
+    *enabled
+}
+
+// Only the current version is written as a document, so that's the only one
+// that can appear in one
+fn supported_version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let version = u32::deserialize(deserializer)?;
+    if version == CURRENT_VERSION {
+        Ok(version)
+    } else {
+        Err(de::Error::custom(format!(
+            "unsupported version {} (expected {})",
+            version, CURRENT_VERSION
+        )))
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Why the reminders file could not be (re)loaded.
+#[derive(Debug, Clone)]
+pub enum ReminderError {
+    /// The file could not be read or written.
+    Io {
+        path: PathBuf,
+        source: Arc<io::Error>,
+    },
+    /// The file is not valid JSON (or TOML, YAML or JSON5), or neither an
+    /// array of objects nor a reminders document with valid settings.
+    Parse {
+        path: PathBuf,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The file parsed, but one or more reminders are invalid.
+    Invalid {
+        path: PathBuf,
+        issues: Vec<ValidationIssue>,
+    },
+    /// The file's `include` names a file that doesn't exist, has an invalid
+    /// pattern, or leads back to the file itself.
+    Include { path: PathBuf, message: String },
+}
+
+/// A problem with a single entry of the reminders file, or with its settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    /// Zero-based position of the entry in the file; `None` for the settings.
+    pub index: Option<usize>,
+    /// The reminder's text, when it could be read.
+    pub text: Option<String>,
+    pub message: String,
+}
+
+impl ReminderError {
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        ReminderError::Io {
+            path: path.into(),
+            source: Arc::new(source),
+        }
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        match self {
+            ReminderError::Io { path, .. }
+            | ReminderError::Parse { path, .. }
+            | ReminderError::Invalid { path, .. }
+            | ReminderError::Include { path, .. } => path,
+        }
+    }
+
+    /// Per-reminder problems, empty unless this is a validation error.
+    pub fn issues(&self) -> &[ValidationIssue] {
+        match self {
+            ReminderError::Invalid { issues, .. } => issues,
+            _ => &[],
+        }
+    }
+}
+
+impl fmt::Display for ReminderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReminderError::Io { path, source } => {
+                write!(f, "{}: {}", path.display(), source)
+            }
+            ReminderError::Parse {
+                path,
+                line,
+                column,
+                message,
+            } => write!(f, "{}:{}:{}: {}", path.display(), line, column, message),
+            ReminderError::Invalid { path, issues } => {
+                // An entry can have several problems
+                let count = issues
+                    .iter()
+                    .filter_map(|issue| issue.index)
+                    .collect::<BTreeSet<_>>()
+                    .len();
+                let noun = if count == 1 { "reminder" } else { "reminders" };
+                let settings = issues.iter().any(|issue| issue.index.is_none());
+                match (count, settings) {
+                    (0, _) => write!(f, "{}: invalid settings", path.display()),
+                    (_, false) => write!(f, "{}: {} invalid {}", path.display(), count, noun),
+                    (_, true) => write!(
+                        f,
+                        "{}: invalid settings and {} invalid {}",
+                        path.display(),
+                        count,
+                        noun
+                    ),
+                }
+            }
+            ReminderError::Include { path, message } => {
+                write!(f, "{}: {}", path.display(), message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReminderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReminderError::Io { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(index) = self.index else {
+            return write!(f, "settings: {}", self.message);
+        };
+        write!(f, "reminder #{}", index + 1)?;
+        if let Some(text) = &self.text {
+            write!(f, " (\"{}\")", text)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
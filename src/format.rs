@@ -0,0 +1,538 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::fmt;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+
+use crate::document::{CURRENT_VERSION, ReminderDocument};
+use crate::error::ReminderError;
+use crate::reminders::{Reminder, strip_position};
+
+/// The syntaxes a reminders file can be written in, chosen by its extension.
+/// Whatever the syntax, the reminders in it are checked the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// JSON; any extension not listed below.
+    Json,
+    /// JSON that may have comments, trailing commas and unquoted keys
+    /// (`.json5`, `.jsonc`).
+    Json5,
+    /// Reminders as a `[[reminders]]` array of tables (`.toml`).
+    Toml,
+    /// YAML (`.yaml`, `.yml`).
+    Yaml,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Self {
+        Self::for_extension(path).unwrap_or(Format::Json)
+    }
+
+    /// The format of a file with one of the extensions listed above, or
+    /// `None` for any other extension. Picks out the reminders files in a
+    /// directory.
+    pub fn for_extension(path: &Path) -> Option<Self> {
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase());
+        match extension.as_deref() {
+            Some("json") => Some(Format::Json),
+            Some("json5" | "jsonc") => Some(Format::Json5),
+            Some("toml") => Some(Format::Toml),
+            Some("yaml" | "yml") => Some(Format::Yaml),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Format::Json => "JSON",
+            Format::Json5 => "JSON5",
+            Format::Toml => "TOML",
+            Format::Yaml => "YAML",
+        }
+    }
+
+    /// Reads a file's content into its settings and reminder entries, leaving
+    /// checking the entries to the caller. A bare array is a version 1 file.
+    pub fn parse_document(
+        &self,
+        path: &Path,
+        content: &str,
+    ) -> Result<ReminderDocument<serde_json::Value>, ReminderError> {
+        let parse_error = |(line, column): (usize, usize), message: String| ReminderError::Parse {
+            path: path.to_path_buf(),
+            line,
+            column,
+            message,
+        };
+
+        if let Format::Toml = self {
+            // A version 1 file only has [[reminders]]
+            let mut table: toml::Table = self.deserialize(path, content)?;
+            if table.keys().any(|key| key != "reminders") {
+                let document: ReminderDocument<toml::Value> = self.deserialize(path, content)?;
+                return Ok(ReminderDocument {
+                    version: document.version,
+                    enabled: document.enabled,
+                    include: document.include,
+                    settings: document.settings,
+                    reminders: document.reminders.into_iter().map(toml_to_json).collect(),
+                });
+            }
+            return match table.remove("reminders") {
+                None => Ok(ReminderDocument::legacy(Vec::new())),
+                Some(toml::Value::Array(entries)) => Ok(ReminderDocument::legacy(
+                    entries.into_iter().map(toml_to_json).collect(),
+                )),
+                Some(_) => Err(parse_error(
+                    (1, 1),
+                    "reminders must be an array of tables, written [[reminders]]".to_string(),
+                )),
+            };
+        }
+
+        match self.deserialize(path, content)? {
+            serde_json::Value::Array(entries) => Ok(ReminderDocument::legacy(entries)),
+            // Parsed again from the text, so errors have positions
+            serde_json::Value::Object(_) => self.deserialize(path, content),
+            _ => Err(parse_error(
+                (1, 1),
+                "expected an array of reminders, or an object with version, settings and reminders"
+                    .to_string(),
+            )),
+        }
+    }
+
+    // Parses the whole of `content` in this format, with the error's position
+    // kept out of its message
+    fn deserialize<T: DeserializeOwned>(
+        &self,
+        path: &Path,
+        content: &str,
+    ) -> Result<T, ReminderError> {
+        let parse_error = |(line, column): (usize, usize), message: String| ReminderError::Parse {
+            path: path.to_path_buf(),
+            line,
+            column,
+            message,
+        };
+
+        match self {
+            Format::Json => serde_json::from_str(content)
+                .map_err(|e| parse_error((e.line(), e.column()), strip_position(&e))),
+            Format::Json5 => json5::from_str(content).map_err(|e| {
+                let json5::Error::Message { msg, location } = e;
+                // Syntax errors draw the line with a caret; keep only the
+                // "= expected ..." part below it
+                let message = msg
+                    .lines()
+                    .find_map(|line| line.trim_start().strip_prefix("= "))
+                    .unwrap_or(&msg);
+                parse_error(
+                    location.map_or((1, 1), |l| (l.line, l.column)),
+                    message.to_string(),
+                )
+            }),
+            Format::Yaml => serde_norway::from_str(content).map_err(|e| {
+                let position = e.location().map_or((1, 1), |l| (l.line(), l.column()));
+                // Like serde_json's, the message includes the position
+                let at = format!(" at line {} column {}", position.0, position.1);
+                parse_error(position, e.to_string().replacen(&at, "", 1))
+            }),
+            Format::Toml => toml::from_str(content).map_err(|e| {
+                let position = e
+                    .span()
+                    .map_or((1, 1), |span| line_column(content, span.start));
+                parse_error(position, e.message().to_string())
+            }),
+        }
+    }
+
+    /// A whole file in this format holding `document`. Version 1 documents
+    /// are written as the bare list of reminders.
+    pub fn serialize(
+        &self,
+        path: &Path,
+        document: &ReminderDocument,
+    ) -> Result<String, ReminderError> {
+        #[derive(Serialize)]
+        struct TomlFile<'a> {
+            reminders: &'a [Reminder],
+        }
+
+        let reminders = &document.reminders;
+        match (self, document.is_legacy()) {
+            // JSON is valid JSON5
+            (Format::Json | Format::Json5, true) => {
+                serde_json::to_string_pretty(reminders).map_err(io::Error::from)
+            }
+            (Format::Json | Format::Json5, false) => {
+                serde_json::to_string_pretty(document).map_err(io::Error::from)
+            }
+            (Format::Toml, true) => {
+                toml::to_string(&TomlFile { reminders }).map_err(io::Error::other)
+            }
+            (Format::Toml, false) => toml::to_string(document).map_err(io::Error::other),
+            (Format::Yaml, true) => serde_norway::to_string(reminders).map_err(io::Error::other),
+            (Format::Yaml, false) => serde_norway::to_string(document).map_err(io::Error::other),
+        }
+        .map_err(|e| ReminderError::io(path, e))
+    }
+
+    /// The text of a version 1 file rewritten as a version 2 document with no
+    /// settings. The original text is kept, comments included, inside the
+    /// new layout; callers should check that it reads back the same.
+    pub fn upgrade_legacy(&self, content: &str) -> String {
+        let indented = |content: &str| -> String {
+            content
+                .trim_end()
+                .lines()
+                .map(|line| match line {
+                    "" => "\n".to_string(),
+                    line => format!("  {}\n", line),
+                })
+                .collect()
+        };
+
+        match self {
+            Format::Json | Format::Json5 => format!(
+                "{{\n  \"version\": {},\n  \"reminders\": {}\n}}\n",
+                CURRENT_VERSION,
+                indented(content).trim()
+            ),
+            Format::Toml => format!("version = {}\n\n{}", CURRENT_VERSION, content),
+            Format::Yaml => format!(
+                "version: {}\nreminders:\n{}",
+                CURRENT_VERSION,
+                indented(content)
+            ),
+        }
+    }
+
+    /// `content` with the entry at `index` replaced by `reminder`, or removed
+    /// when it's `None`; `index` past the end appends. Only that entry's text
+    /// changes, so the rest of the file keeps its layout and comments. `None`
+    /// if the edit can't be made that way.
+    pub fn splice_entry(
+        &self,
+        content: &str,
+        index: usize,
+        reminder: Option<&Reminder>,
+    ) -> Option<String> {
+        match self {
+            Format::Json => splice_json(content, index, reminder),
+            Format::Toml => splice_toml(content, index, reminder),
+            Format::Json5 | Format::Yaml => None,
+        }
+    }
+
+    /// Whether `content` has comments, which writing the file out again
+    /// from its reminders would lose. A `#` or `//` inside a string isn't one.
+    pub fn has_comments(&self, content: &str) -> bool {
+        match self {
+            Format::Json => false,
+            Format::Json5 => has_slash_comments(content),
+            Format::Toml => has_hash_comments(content, false),
+            Format::Yaml => has_hash_comments(content, true),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// One-based line and column of a byte offset
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+// TOML dates and times become strings, as they're written in JSON
+fn toml_to_json(value: toml::Value) -> serde_json::Value {
+    match value {
+        toml::Value::String(s) => s.into(),
+        toml::Value::Integer(i) => i.into(),
+        toml::Value::Float(f) => f.into(),
+        toml::Value::Boolean(b) => b.into(),
+        toml::Value::Datetime(datetime) => datetime.to_string().into(),
+        toml::Value::Array(values) => values.into_iter().map(toml_to_json).collect(),
+        toml::Value::Table(table) => table
+            .into_iter()
+            .map(|(key, value)| (key, toml_to_json(value)))
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+    }
+}
+
+// Edits the [[reminders]] tables through toml_edit, which keeps everything it
+// doesn't touch. An edited entry keeps its comments too: only the keys that
+// changed are rewritten, each in place. `None` if `reminders` isn't an array
+// of tables.
+fn splice_toml(content: &str, index: usize, reminder: Option<&Reminder>) -> Option<String> {
+    let mut document: toml_edit::DocumentMut = content.parse().ok()?;
+    let tables = document
+        .entry("reminders")
+        .or_insert(toml_edit::Item::ArrayOfTables(Default::default()))
+        .as_array_of_tables_mut()?;
+
+    match reminder {
+        Some(reminder) => {
+            let entry: toml_edit::DocumentMut = toml::to_string(reminder).ok()?.parse().ok()?;
+            let entry = entry.as_table().clone();
+            match tables.get_mut(index) {
+                Some(existing) => update_table(existing, entry),
+                None => tables.push(entry),
+            }
+        }
+        None if index < tables.len() => {
+            tables.remove(index);
+        }
+        None => return None,
+    }
+    Some(document.to_string())
+}
+
+// Makes `existing` hold the keys of `entry`. Keys whose values are the same
+// are left as written; a changed value keeps the comment after it, and a
+// changed key the comments above it.
+fn update_table(existing: &mut toml_edit::Table, entry: toml_edit::Table) {
+    let unset: Vec<String> = existing
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| !entry.contains_key(key))
+        .collect();
+    for key in unset {
+        existing.remove(&key);
+    }
+
+    for (key, item) in entry {
+        match (existing.get_mut(&key), item) {
+            (Some(toml_edit::Item::Value(old)), toml_edit::Item::Value(mut new)) => {
+                if !same_value(old, &new) {
+                    *new.decor_mut() = old.decor().clone();
+                    *old = new;
+                }
+            }
+            (Some(old), item) => *old = item,
+            (None, item) => {
+                existing.insert(&key, item);
+            }
+        }
+    }
+}
+
+// Whether two values read the same, however they're written
+fn same_value(a: &toml_edit::Value, b: &toml_edit::Value) -> bool {
+    let read = |value: &toml_edit::Value| {
+        let mut value = value.clone();
+        value.decor_mut().clear();
+        toml::from_str::<toml::Table>(&format!("value = {}", value)).ok()
+    };
+    read(a).is_some_and(|a| Some(a) == read(b))
+}
+
+// Edits the text of the reminders array, at the top level or in a document,
+// directly. New entries copy the layout of their neighbours. `None` if there's
+// no array this can edit, or the edit would leave it empty.
+fn splice_json(content: &str, index: usize, reminder: Option<&Reminder>) -> Option<String> {
+    #[derive(Deserialize)]
+    struct Document<'a> {
+        #[serde(borrow)]
+        reminders: Vec<&'a RawValue>,
+    }
+
+    let entries: Vec<&RawValue> = serde_json::from_str(content)
+        .or_else(|_| serde_json::from_str(content).map(|document: Document| document.reminders))
+        .ok()?;
+    let spans: Vec<Range<usize>> = entries
+        .iter()
+        .map(|entry| {
+            let start = entry.get().as_ptr() as usize - content.as_ptr() as usize;
+            start..start + entry.get().len()
+        })
+        .collect();
+    let last = spans.last()?;
+
+    match reminder {
+        Some(reminder) if index < spans.len() => {
+            let entry = format_entry(content, &spans[index], reminder)?;
+            Some(format!(
+                "{}{}{}",
+                &content[..spans[index].start],
+                entry,
+                &content[spans[index].end..]
+            ))
+        }
+        Some(reminder) => {
+            let entry = format_entry(content, last, reminder)?;
+            let separator = match spans.len() {
+                1 => format!(",\n{}", indentation(content, last)),
+                n => content[spans[n - 2].end..last.start].to_string(),
+            };
+            Some(format!(
+                "{}{}{}{}",
+                &content[..last.end],
+                separator,
+                entry,
+                &content[last.end..]
+            ))
+        }
+        None if spans.len() < 2 || index >= spans.len() => None,
+        // Take the separator after the entry with it, or before the last one
+        None if index + 1 < spans.len() => Some(format!(
+            "{}{}",
+            &content[..spans[index].start],
+            &content[spans[index + 1].start..]
+        )),
+        None => Some(format!(
+            "{}{}",
+            &content[..spans[index - 1].end],
+            &content[spans[index].end..]
+        )),
+    }
+}
+
+// `reminder` as JSON laid out like the entry at `like`: on one line, spaced
+// the same, if that is, otherwise pretty-printed at its indentation
+fn format_entry(content: &str, like: &Range<usize>, reminder: &Reminder) -> Option<String> {
+    let entry = &content[like.clone()];
+    if !entry.contains('\n') {
+        if !entry.contains("\": ") {
+            return serde_json::to_string(reminder).ok();
+        }
+        let mut json = Vec::new();
+        reminder
+            .serialize(&mut serde_json::Serializer::with_formatter(
+                &mut json,
+                SpacedFormatter,
+            ))
+            .ok()?;
+        return String::from_utf8(json).ok();
+    }
+
+    let indent = indentation(content, like);
+    // The step is how much further in the entry's first field is
+    let step = entry
+        .lines()
+        .nth(1)
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .and_then(|field| field.strip_prefix(indent))
+        .filter(|step| !step.is_empty())
+        .unwrap_or("  ");
+    let mut json = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(step.as_bytes());
+    reminder
+        .serialize(&mut serde_json::Serializer::with_formatter(
+            &mut json, formatter,
+        ))
+        .ok()?;
+    let json = String::from_utf8(json).ok()?;
+    Some(json.replace('\n', &format!("\n{}", indent)))
+}
+
+// JSON on one line with a space after each `,` and `:`
+struct SpacedFormatter;
+
+impl serde_json::ser::Formatter for SpacedFormatter {
+    fn begin_array_value<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        if first {
+            Ok(())
+        } else {
+            writer.write_all(b", ")
+        }
+    }
+
+    fn begin_object_key<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        if first {
+            Ok(())
+        } else {
+            writer.write_all(b", ")
+        }
+    }
+
+    fn begin_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(b": ")
+    }
+}
+
+// Whether there's a `//` or `/* */` comment outside the strings
+fn has_slash_comments(content: &str) -> bool {
+    let mut chars = content.chars().peekable();
+    let mut quote = None;
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '/') if matches!(chars.peek(), Some('/' | '*')) => return true,
+            (None, _) => {}
+        }
+    }
+    false
+}
+
+// Whether there's a `#` comment outside the strings: one at the start of a
+// line or after whitespace. In YAML a quote only starts a string at the start
+// of a value, so the apostrophe in `text: Don't` doesn't.
+fn has_hash_comments(content: &str, plain_scalars: bool) -> bool {
+    let mut chars = content.chars().peekable();
+    let mut quote = None;
+    // The character before, and the last one on the line that isn't space
+    let (mut previous, mut token) = ('\n', '\n');
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some('"'), '\\') => {
+                chars.next();
+            }
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '#') if previous.is_whitespace() => return true,
+            (None, '"' | '\'')
+                if !plain_scalars || matches!(token, '\n' | ':' | '-' | '?' | '[' | '{' | ',') =>
+            {
+                quote = Some(c)
+            }
+            (None, _) => {}
+        }
+        previous = c;
+        if c == '\n' {
+            token = '\n';
+        } else if !c.is_whitespace() {
+            token = c;
+        }
+    }
+    false
+}
+
+// The whitespace before an entry on its line, or nothing if the line has
+// anything else before it
+fn indentation<'a>(content: &'a str, span: &Range<usize>) -> &'a str {
+    let line_start = content[..span.start].rfind('\n').map_or(0, |i| i + 1);
+    let before = &content[line_start..span.start];
+    if before.chars().all(char::is_whitespace) {
+        before
+    } else {
+        ""
+    }
+}
@@ -0,0 +1,289 @@
+use chrono::{Days, NaiveDate};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::ReminderError;
+use crate::recurrence::Recurrence;
+use crate::reminders::strip_position;
+
+/// A public holiday or other non-working day, possibly spanning several days
+/// or repeating.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Holiday {
+    pub name: String,
+    start: NaiveDate,
+    length_days: u64,
+    recurrence: Option<Recurrence>,
+}
+
+impl Holiday {
+    pub fn new(name: impl Into<String>, date: NaiveDate) -> Self {
+        Holiday {
+            name: name.into(),
+            start: date,
+            length_days: 1,
+            recurrence: None,
+        }
+    }
+
+    /// Whether `date` is one of the days of this holiday.
+    pub fn falls_on(&self, date: NaiveDate) -> bool {
+        // A multi-day holiday covers `date` if it started up to length-1 days before
+        (0..self.length_days)
+            .filter_map(|offset| date.checked_sub_days(Days::new(offset)))
+            .any(|start| match &self.recurrence {
+                Some(recurrence) => recurrence.occurs_on(start),
+                None => start == self.start,
+            })
+    }
+}
+
+/// The set of holidays that `skip_holidays` and `only_on_holidays` refer to.
+///
+/// Loaded from an iCalendar file (`.ics`) or a JSON array of dates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HolidayCalendar {
+    holidays: Vec<Holiday>,
+}
+
+impl HolidayCalendar {
+    pub fn new(holidays: Vec<Holiday>) -> Self {
+        HolidayCalendar { holidays }
+    }
+
+    /// Reads a calendar file, choosing the format from its extension: `.ics`
+    /// is iCalendar, anything else JSON.
+    pub fn load(path: &Path) -> Result<Self, ReminderError> {
+        let content = fs::read_to_string(path).map_err(|e| ReminderError::io(path, e))?;
+        let is_ics = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("ics"));
+        if is_ics {
+            Self::parse_ics(path, &content)
+        } else {
+            Self::parse_json(path, &content)
+        }
+    }
+
+    /// Parses a JSON array whose entries are either a date (`"2026-12-25"`)
+    /// or an object with `date`, an optional `name` and an optional inclusive
+    /// `end_date` for holidays longer than a day.
+    pub fn parse_json(path: &Path, content: &str) -> Result<Self, ReminderError> {
+        let entries: Vec<JsonHoliday> =
+            serde_json::from_str(content).map_err(|e| ReminderError::Parse {
+                path: path.to_path_buf(),
+                line: e.line(),
+                column: e.column(),
+                message: strip_position(&e),
+            })?;
+
+        let holidays = entries.into_iter().map(Holiday::from).collect();
+        Ok(HolidayCalendar { holidays })
+    }
+
+    /// Parses the VEVENTs of an iCalendar file, as exported by most calendar
+    /// applications. Each event's DTSTART, DTEND (exclusive), SUMMARY and
+    /// RRULE are used; everything else is ignored.
+    pub fn parse_ics(path: &Path, content: &str) -> Result<Self, ReminderError> {
+        let error = |line: usize, message: String| ReminderError::Parse {
+            path: path.to_path_buf(),
+            line,
+            column: 1,
+            message,
+        };
+
+        let mut holidays = Vec::new();
+        let mut event: Option<IcsEvent> = None;
+        for (line, property) in unfold(content) {
+            let Some((name_and_params, value)) = property.split_once(':') else {
+                continue;
+            };
+            let name = name_and_params
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .to_uppercase();
+
+            match (name.as_str(), event.as_mut()) {
+                ("BEGIN", _) if value.eq_ignore_ascii_case("VEVENT") => {
+                    event = Some(IcsEvent {
+                        line,
+                        ..Default::default()
+                    });
+                }
+                ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                    let finished = event.take().unwrap_or_default();
+                    holidays.push(
+                        finished
+                            .into_holiday()
+                            .map_err(|(line, m)| error(line, m))?,
+                    );
+                }
+                ("DTSTART", Some(event)) => {
+                    event.start = Some(parse_ics_date(value).map_err(|m| error(line, m))?);
+                }
+                ("DTEND", Some(event)) => {
+                    let end = parse_ics_date(value).map_err(|m| error(line, m))?;
+                    // A timed event ending later the same day still covers that day
+                    event.end = Some(if value.contains('T') {
+                        end.succ_opt().unwrap_or(end)
+                    } else {
+                        end
+                    });
+                }
+                ("SUMMARY", Some(event)) => event.summary = unescape(value),
+                ("RRULE", Some(event)) => event.rrule = Some((line, value.to_string())),
+                _ => {}
+            }
+        }
+
+        if let Some(event) = event {
+            return Err(error(
+                event.line,
+                "VEVENT is missing END:VEVENT".to_string(),
+            ));
+        }
+        Ok(HolidayCalendar { holidays })
+    }
+
+    /// Whether `date` is a holiday.
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holiday_on(date).is_some()
+    }
+
+    /// The name of the holiday on `date`, if there is one.
+    pub fn holiday_on(&self, date: NaiveDate) -> Option<&str> {
+        self.holidays
+            .iter()
+            .find(|holiday| holiday.falls_on(date))
+            .map(|holiday| holiday.name.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.holidays.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.holidays.is_empty()
+    }
+}
+
+/// The holiday calendar next to the reminders file, if there is one:
+/// `holidays.ics` or else `holidays.json`.
+pub fn find_holidays_file(reminders_file: &Path) -> Option<PathBuf> {
+    let dir = reminders_file.parent().unwrap_or(Path::new(""));
+    ["holidays.ics", "holidays.json"]
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonHoliday {
+    Date(NaiveDate),
+    Detailed {
+        date: NaiveDate,
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        end_date: Option<NaiveDate>,
+    },
+}
+
+impl From<JsonHoliday> for Holiday {
+    fn from(entry: JsonHoliday) -> Self {
+        match entry {
+            JsonHoliday::Date(date) => Holiday::new("", date),
+            JsonHoliday::Detailed {
+                date,
+                name,
+                end_date,
+            } => Holiday {
+                name,
+                start: date,
+                // An end_date before date is treated as a single day
+                length_days: end_date
+                    .and_then(|end| u64::try_from((end - date).num_days() + 1).ok())
+                    .unwrap_or(1)
+                    .max(1),
+                recurrence: None,
+            },
+        }
+    }
+}
+
+#[derive(Default)]
+struct IcsEvent {
+    line: usize,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    summary: String,
+    rrule: Option<(usize, String)>,
+}
+
+impl IcsEvent {
+    /// On failure, returns the line to report along with the message.
+    fn into_holiday(self) -> Result<Holiday, (usize, String)> {
+        let start = self
+            .start
+            .ok_or_else(|| (self.line, "VEVENT has no DTSTART".to_string()))?;
+        let length_days = self
+            .end
+            .and_then(|end| u64::try_from((end - start).num_days()).ok())
+            .unwrap_or(1)
+            .max(1);
+
+        let recurrence = match self.rrule {
+            Some((line, rule)) => {
+                let source = format!("DTSTART:{}\nRRULE:{}", start.format("%Y%m%d"), rule);
+                Some(
+                    source
+                        .parse::<Recurrence>()
+                        .map_err(|e| (line, e.to_string()))?,
+                )
+            }
+            None => None,
+        };
+
+        Ok(Holiday {
+            name: self.summary,
+            start,
+            length_days,
+            recurrence,
+        })
+    }
+}
+
+/// Joins folded lines (continuations start with a space or tab), yielding each
+/// logical line with the one-based number of the line it starts on.
+fn unfold(content: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (i, raw) in content.lines().enumerate() {
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some((_, line))) => line.push_str(continuation),
+            _ => lines.push((i + 1, raw.to_string())),
+        }
+    }
+    lines
+}
+
+// Accepts DATE and DATE-TIME values, e.g. 20261225 or 20261225T090000Z
+fn parse_ics_date(value: &str) -> Result<NaiveDate, String> {
+    value
+        .trim()
+        .get(..8)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+        .ok_or_else(|| format!("invalid date \"{}\"", value.trim()))
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .trim()
+        .replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
@@ -1,3 +1,32 @@
+pub mod clock;
+pub mod cron;
+pub mod document;
+pub mod error;
+pub mod format;
+pub mod holidays;
+pub mod recurrence;
 pub mod reminders;
+pub mod rotation;
+pub mod schedule;
+pub mod settings;
+pub mod source;
+pub mod state;
+pub mod watcher;
 
-pub use reminders::{Reminder, ReminderManager};
\ No newline at end of file
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use cron::{CronExpression, CronSchedule};
+pub use document::{CURRENT_VERSION, ReminderDocument};
+pub use error::{ReminderError, ValidationIssue};
+pub use format::Format;
+pub use holidays::{Holiday, HolidayCalendar};
+pub use recurrence::Recurrence;
+pub use reminders::{
+    DEFAULT_ROTATION_INTERVAL, Reminder, ReminderManager, lint_reminders, migrate_document,
+    parse_document, parse_reminders,
+};
+pub use rotation::{RotationKind, RotationStrategy};
+pub use schedule::{Period, Priority, ScheduleContext, TimeWindow, TimeWindows, Weekday};
+pub use settings::{MAX_SNOOZE_LENGTHS, Settings, Theme};
+pub use source::{SourceFile, lint_sources, read_sources};
+pub use state::{DEFAULT_SNOOZE_MINUTES, ReminderState};
+pub use watcher::ReminderWatcher;
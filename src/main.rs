@@ -1,42 +1,131 @@
+mod cli;
+mod commands;
+
+use clap::{CommandFactory, Parser};
+use cli::Cli;
 use eframe::egui;
+use reminder_display::holidays::{self, HolidayCalendar};
+use reminder_display::{
+    Clock, DEFAULT_SNOOZE_MINUTES, MAX_SNOOZE_LENGTHS, ReminderError, ReminderManager,
+    ReminderWatcher, SystemClock, Theme,
+};
+use std::process::ExitCode;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::time;
 
-mod reminders;
-use reminders::ReminderManager;
+// Only used when the file watcher can't be started
+const POLL_INTERVAL_TICKS: u32 = 10;
+
+// Keys 1, 2, 3... pick the first, second, third... snooze length
+const SNOOZE_KEYS: [egui::Key; MAX_SNOOZE_LENGTHS] = [
+    egui::Key::Num1,
+    egui::Key::Num2,
+    egui::Key::Num3,
+    egui::Key::Num4,
+    egui::Key::Num5,
+    egui::Key::Num6,
+    egui::Key::Num7,
+    egui::Key::Num8,
+    egui::Key::Num9,
+];
 
 struct ReminderDisplayApp {
     reminder_manager: Arc<Mutex<ReminderManager>>,
+    _watcher: Option<ReminderWatcher>,
+    // From the command line, over the file's settings
+    cli_theme: Option<Theme>,
+    cli_snooze_minutes: Option<Vec<u32>>,
+    theme: Option<Theme>,
+    snooze_minutes: Vec<u32>,
 }
 
 impl ReminderDisplayApp {
-    fn new(_cc: &eframe::CreationContext<'_>) -> Self {
-        let reminder_manager = Arc::new(Mutex::new(ReminderManager::new()));
+    fn new(cc: &eframe::CreationContext<'_>, manager: ReminderManager, cli: &Cli) -> Self {
+        let reminder_manager = Arc::new(Mutex::new(manager));
+
+        // Reload as soon as the file changes on disk
+        let ctx = cc.egui_ctx.clone();
+        let watcher =
+            match ReminderWatcher::spawn(reminder_manager.clone(), move || ctx.request_repaint()) {
+                Ok(watcher) => Some(watcher),
+                Err(e) => {
+                    log::warn!("File watcher unavailable, falling back to polling: {}", e);
+                    None
+                }
+            };
+        let poll_for_updates = watcher.is_none();
 
-        // Start background file watcher and rotation
+        // Start background rotation
         let manager_clone = reminder_manager.clone();
         tokio::spawn(async move {
-            let mut interval = time::interval(Duration::from_secs(10));
+            let mut interval = time::interval(Duration::from_secs(1));
+            let mut ticks = 0u32;
             loop {
                 interval.tick().await;
+                ticks = ticks.wrapping_add(1);
                 if let Ok(mut manager) = manager_clone.lock() {
-                    manager.check_for_updates();
+                    if poll_for_updates && ticks.is_multiple_of(POLL_INTERVAL_TICKS) {
+                        manager.check_for_updates();
+                    }
                     manager.rotate_if_needed();
                 }
             }
         });
 
-        Self { reminder_manager }
+        Self {
+            reminder_manager,
+            _watcher: watcher,
+            cli_theme: cli.theme,
+            cli_snooze_minutes: cli.snooze_minutes.clone(),
+            theme: None,
+            snooze_minutes: DEFAULT_SNOOZE_MINUTES.to_vec(),
+        }
+    }
+
+    // Follows the file's settings as it's reloaded
+    fn apply_settings(&mut self, ctx: &egui::Context) {
+        let Ok(manager) = self.reminder_manager.lock() else {
+            return;
+        };
+        let settings = manager.settings();
+        let theme = self.cli_theme.or(settings.theme).unwrap_or_default();
+        self.snooze_minutes = match (&self.cli_snooze_minutes, &settings.snooze_minutes) {
+            (Some(minutes), _) => minutes.clone(),
+            (None, Some(minutes)) => minutes.iter().map(|m| m.get()).collect(),
+            (None, None) => DEFAULT_SNOOZE_MINUTES.to_vec(),
+        };
+        drop(manager);
+
+        if self.theme != Some(theme) {
+            ctx.set_theme(theme);
+            self.theme = Some(theme);
+        }
     }
 }
 
 impl eframe::App for ReminderDisplayApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         ctx.request_repaint_after(Duration::from_secs(1));
+        self.apply_settings(ctx);
+
+        // Space or Enter acknowledges the reminder on screen
+        let mut acknowledge =
+            ctx.input(|i| i.key_pressed(egui::Key::Space) || i.key_pressed(egui::Key::Enter));
+        let mut snooze = ctx.input(|i| {
+            self.snooze_minutes
+                .iter()
+                .zip(SNOOZE_KEYS)
+                .find(|(_, key)| i.key_pressed(*key))
+                .map(|(minutes, _)| *minutes)
+        });
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            if let Ok(manager) = self.reminder_manager.lock() {
+            if let Ok(mut manager) = self.reminder_manager.lock() {
+                if let Some(error) = manager.last_error() {
+                    show_error_banner(ui, error);
+                }
+
                 ui.vertical_centered(|ui| {
                     // Current time
                     ui.add_space(20.0);
@@ -72,6 +161,36 @@ impl eframe::App for ReminderDisplayApp {
                                             .color(egui::Color32::GRAY),
                                     );
                                 }
+
+                                if let Some(cron) = &reminder.cron {
+                                    ui.label(
+                                        egui::RichText::new(format!("⏰ {}", cron))
+                                            .size(20.0)
+                                            .color(egui::Color32::GRAY),
+                                    );
+                                }
+                            });
+
+                            ui.add_space(20.0);
+
+                            // Large enough to tap on a touch screen
+                            ui.horizontal(|ui| {
+                                let done =
+                                    egui::Button::new(egui::RichText::new("✔ Done").size(24.0))
+                                        .min_size(egui::vec2(160.0, 48.0));
+                                if ui.add(done).clicked() {
+                                    acknowledge = true;
+                                }
+
+                                for minutes in &self.snooze_minutes {
+                                    let label = format!("💤 {} min", minutes);
+                                    let button =
+                                        egui::Button::new(egui::RichText::new(label).size(24.0))
+                                            .min_size(egui::vec2(120.0, 48.0));
+                                    if ui.add(button).clicked() {
+                                        snooze = Some(*minutes);
+                                    }
+                                }
                             });
                         });
                     } else {
@@ -113,26 +232,129 @@ impl eframe::App for ReminderDisplayApp {
                         });
                     });
                 });
+
+                if acknowledge && let Err(e) = manager.acknowledge_current() {
+                    log::error!("Could not save acknowledgement: {}", e);
+                }
+                if let Some(minutes) = snooze
+                    && let Err(e) = manager.snooze_current(minutes)
+                {
+                    log::error!("Could not save snooze: {}", e);
+                }
             }
         });
     }
 }
 
+// How many per-reminder problems to list before summarising the rest
+const MAX_BANNER_ISSUES: usize = 3;
+
+fn show_error_banner(ui: &mut egui::Ui, error: &ReminderError) {
+    egui::Frame::new()
+        .fill(egui::Color32::from_rgb(120, 20, 20))
+        .inner_margin(egui::Margin::same(10))
+        .corner_radius(6.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.label(
+                egui::RichText::new(format!("⚠ Reminders file not loaded: {}", error))
+                    .size(20.0)
+                    .color(egui::Color32::WHITE),
+            );
+
+            let issues = error.issues();
+            for issue in issues.iter().take(MAX_BANNER_ISSUES) {
+                ui.label(
+                    egui::RichText::new(format!("• {}", issue))
+                        .size(16.0)
+                        .color(egui::Color32::WHITE),
+                );
+            }
+            if issues.len() > MAX_BANNER_ISSUES {
+                ui.label(
+                    egui::RichText::new(format!("…and {} more", issues.len() - MAX_BANNER_ISSUES))
+                        .size(16.0)
+                        .color(egui::Color32::WHITE),
+                );
+            }
+        });
+}
+
+fn create_manager(cli: &Cli, clock: Arc<dyn Clock>) -> ReminderManager {
+    let manager = match &cli.file {
+        Some(path) => ReminderManager::with_file(path.to_string_lossy(), clock),
+        None => ReminderManager::with_clock(clock),
+    };
+    match manager.last_error() {
+        Some(e) => log::error!("{}", e),
+        None => log::info!(
+            "Loaded {} reminders from {}",
+            manager.list().len(),
+            manager.file_path().display()
+        ),
+    }
+    configure_manager(cli, manager)
+}
+
+// Applies the options other than the file to a loaded manager
+fn configure_manager(cli: &Cli, mut manager: ReminderManager) -> ReminderManager {
+    // Whatever isn't given comes from the file's settings
+    if let Some(interval) = cli.interval {
+        manager.set_rotation_interval(interval);
+    }
+    if let Some(rotation) = cli.rotation {
+        manager.set_rotation_strategy(rotation.create());
+    }
+    manager.set_default_timezone(cli.timezone);
+
+    let holidays_file = cli
+        .holidays
+        .clone()
+        .or_else(|| holidays::find_holidays_file(manager.file_path()));
+    if let Some(path) = holidays_file {
+        match HolidayCalendar::load(&path) {
+            Ok(calendar) => {
+                log::info!("Loaded {} holidays from {}", calendar.len(), path.display());
+                manager.set_holidays(Some(calendar));
+            }
+            Err(e) => log::warn!("Holiday calendar not loaded: {}", e),
+        }
+    }
+    manager
+}
+
 #[tokio::main]
-async fn main() -> Result<(), eframe::Error> {
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if let Err(e) = cli.validate() {
+        Cli::command()
+            .error(clap::error::ErrorKind::InvalidValue, e)
+            .exit();
+    }
+    env_logger::Builder::new()
+        .filter_level(cli.log_level)
+        .init();
+
+    if let Some(command) = &cli.command {
+        return commands::run(command, &cli);
+    }
+
+    let manager = create_manager(&cli, Arc::new(SystemClock));
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_inner_size([800.0, 480.0])
-            .with_fullscreen(true)
-            .with_always_on_top()
-            .with_decorations(false)
-            .with_resizable(false),
+        viewport: cli.viewport(),
         ..Default::default()
     };
 
-    eframe::run_native(
+    let result = eframe::run_native(
         "Work Reminders",
         options,
-        Box::new(|cc| Ok(Box::new(ReminderDisplayApp::new(cc)))),
-    )
+        Box::new(move |cc| Ok(Box::new(ReminderDisplayApp::new(cc, manager, &cli)))),
+    );
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
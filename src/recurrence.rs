@@ -0,0 +1,490 @@
+use chrono::{Datelike, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::schedule::ParseScheduleError;
+
+/// An RFC 5545 recurrence rule with its start date, e.g.
+///
+/// ```text
+/// DTSTART:20260106
+/// RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU
+/// ```
+///
+/// The two parts may also be separated by a space instead of a newline.
+/// Recurrences are evaluated per day: they decide on which dates a reminder
+/// is shown, and the reminder's `time_range` decides when during those days.
+/// Supported rule parts are FREQ (DAILY, WEEKLY, MONTHLY, YEARLY), INTERVAL,
+/// COUNT, UNTIL, BYDAY (with ordinals such as `-1FR`), BYMONTHDAY, BYMONTH,
+/// BYSETPOS and WKST.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Recurrence {
+    source: String,
+    dtstart: NaiveDate,
+    rule: Rule,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<NaiveDate>,
+    /// Weekdays with an optional ordinal, e.g. `-1FR` is `(Some(-1), Fri)`
+    by_day: Vec<(Option<i32>, Weekday)>,
+    by_month_day: Vec<i32>,
+    by_month: Vec<u32>,
+    by_set_pos: Vec<i32>,
+    week_start: Weekday,
+}
+
+/// The most periods (days, weeks, months or years the interval selects) a
+/// COUNT rule can take to reach its count. A rule that takes longer, or never
+/// gets there, is rejected; UNTIL has no such limit.
+pub const MAX_COUNTED_PERIODS: i64 = 10_000;
+
+impl Recurrence {
+    pub fn dtstart(&self) -> NaiveDate {
+        self.dtstart
+    }
+
+    /// Whether the rule produces an occurrence on `date`.
+    pub fn occurs_on(&self, date: NaiveDate) -> bool {
+        if date < self.dtstart || self.rule.until.is_some_and(|until| date > until) {
+            return false;
+        }
+
+        let period = self.period_index(date);
+        if period % i64::from(self.rule.interval) != 0 {
+            return false;
+        }
+
+        self.occurrences_in_period(period).contains(&date)
+    }
+
+    /// Number of whole periods (days, weeks, months or years) between the
+    /// start date and `date`.
+    fn period_index(&self, date: NaiveDate) -> i64 {
+        let start = self.dtstart;
+        match self.rule.frequency {
+            Frequency::Daily => (date - start).num_days(),
+            Frequency::Weekly => {
+                (self.week_start_of(date) - self.week_start_of(start)).num_days() / 7
+            }
+            Frequency::Monthly => {
+                i64::from(date.year() - start.year()) * 12 + i64::from(date.month())
+                    - i64::from(start.month())
+            }
+            Frequency::Yearly => i64::from(date.year() - start.year()),
+        }
+    }
+
+    fn week_start_of(&self, date: NaiveDate) -> NaiveDate {
+        let offset = (7 + date.weekday().num_days_from_monday()
+            - self.rule.week_start.num_days_from_monday())
+            % 7;
+        date - chrono::Duration::days(i64::from(offset))
+    }
+
+    /// All occurrences in the given period, sorted, before COUNT is applied.
+    fn occurrences_in_period(&self, period: i64) -> Vec<NaiveDate> {
+        let rule = &self.rule;
+        let start = self.dtstart;
+        let mut dates = match rule.frequency {
+            Frequency::Daily => {
+                let day = start + chrono::Duration::days(period);
+                let matches = rule.by_month_matches(day)
+                    && (rule.by_month_day.is_empty() || month_day_matches(&rule.by_month_day, day))
+                    && (rule.by_day.is_empty()
+                        || rule.by_day.iter().any(|(_, wd)| *wd == day.weekday()));
+                if matches { vec![day] } else { Vec::new() }
+            }
+            Frequency::Weekly => {
+                let week_start = self.week_start_of(start) + chrono::Duration::weeks(period);
+                let weekdays: Vec<Weekday> = if rule.by_day.is_empty() {
+                    vec![start.weekday()]
+                } else {
+                    rule.by_day.iter().map(|(_, wd)| *wd).collect()
+                };
+                (0..7)
+                    .map(|i| week_start + chrono::Duration::days(i))
+                    .filter(|d| weekdays.contains(&d.weekday()) && rule.by_month_matches(*d))
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let Some(first) = first_of_month(start, period) else {
+                    return Vec::new();
+                };
+                if !rule.by_month_matches(first) {
+                    return Vec::new();
+                }
+                self.expand_month(first)
+            }
+            Frequency::Yearly => {
+                let year = start.year() + period as i32;
+                if rule.by_month.is_empty()
+                    && !rule.by_day.is_empty()
+                    && rule.by_month_day.is_empty()
+                {
+                    // BYDAY ordinals count within the whole year
+                    let first = NaiveDate::from_ymd_opt(year, 1, 1);
+                    let last = NaiveDate::from_ymd_opt(year, 12, 31);
+                    match (first, last) {
+                        (Some(first), Some(last)) => expand_by_day(&rule.by_day, first, last),
+                        _ => Vec::new(),
+                    }
+                } else {
+                    let months: Vec<u32> = if !rule.by_month.is_empty() {
+                        rule.by_month.clone()
+                    } else if !rule.by_month_day.is_empty() {
+                        (1..=12).collect()
+                    } else {
+                        vec![start.month()]
+                    };
+                    let mut dates: Vec<NaiveDate> = months
+                        .into_iter()
+                        .filter_map(|month| NaiveDate::from_ymd_opt(year, month, 1))
+                        .flat_map(|first| self.expand_month(first))
+                        .collect();
+                    dates.sort();
+                    dates
+                }
+            }
+        };
+
+        dates.dedup();
+        if !rule.by_set_pos.is_empty() {
+            dates = select_positions(&dates, &rule.by_set_pos);
+        }
+        // Nothing recurs before DTSTART
+        dates.retain(|d| *d >= start && rule.until.is_none_or(|until| *d <= until));
+        dates
+    }
+
+    /// Days in the month starting at `first` selected by BYMONTHDAY and BYDAY,
+    /// or the start date's day of the month when neither is given.
+    fn expand_month(&self, first: NaiveDate) -> Vec<NaiveDate> {
+        let rule = &self.rule;
+        let last = last_of_month(first);
+
+        if rule.by_day.is_empty() && rule.by_month_day.is_empty() {
+            return first.with_day(self.dtstart.day()).into_iter().collect();
+        }
+
+        let mut dates: Vec<NaiveDate> = if rule.by_day.is_empty() {
+            first
+                .iter_days()
+                .take_while(|d| *d <= last)
+                .filter(|d| month_day_matches(&rule.by_month_day, *d))
+                .collect()
+        } else {
+            expand_by_day(&rule.by_day, first, last)
+        };
+        // BYDAY limits BYMONTHDAY when both are present
+        if !rule.by_day.is_empty() && !rule.by_month_day.is_empty() {
+            dates.retain(|d| month_day_matches(&rule.by_month_day, *d));
+        }
+        dates.sort();
+        dates
+    }
+
+    /// The date of the `count`th occurrence, if it's within
+    /// [`MAX_COUNTED_PERIODS`] of the periods the interval selects.
+    fn last_counted(&self, count: u32) -> Option<NaiveDate> {
+        let interval = i64::from(self.rule.interval);
+        let mut left = count as usize;
+        for period in (0..MAX_COUNTED_PERIODS).map(|n| n * interval) {
+            let occurrences = self.occurrences_in_period(period);
+            if left <= occurrences.len() {
+                return occurrences.get(left.checked_sub(1)?).copied();
+            }
+            left -= occurrences.len();
+        }
+        None
+    }
+}
+
+impl Rule {
+    fn by_month_matches(&self, date: NaiveDate) -> bool {
+        self.by_month.is_empty() || self.by_month.contains(&date.month())
+    }
+}
+
+fn first_of_month(start: NaiveDate, months_after: i64) -> Option<NaiveDate> {
+    let first = start.with_day(1)?;
+    first.checked_add_months(Months::new(u32::try_from(months_after).ok()?))
+}
+
+fn last_of_month(first: NaiveDate) -> NaiveDate {
+    first
+        .checked_add_months(Months::new(1))
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(first)
+}
+
+fn month_day_matches(month_days: &[i32], date: NaiveDate) -> bool {
+    let days_in_month = last_of_month(date.with_day(1).unwrap_or(date)).day() as i32;
+    let day = date.day() as i32;
+    month_days
+        .iter()
+        .any(|&md| md == day || (md < 0 && days_in_month + md + 1 == day))
+}
+
+/// Expands BYDAY entries within `first..=last`. Entries with an ordinal pick
+/// the nth (or nth-from-last) such weekday; plain entries pick all of them.
+fn expand_by_day(
+    by_day: &[(Option<i32>, Weekday)],
+    first: NaiveDate,
+    last: NaiveDate,
+) -> Vec<NaiveDate> {
+    let mut dates = Vec::new();
+    for &(ordinal, weekday) in by_day {
+        let matching: Vec<NaiveDate> = first
+            .iter_days()
+            .take_while(|d| *d <= last)
+            .filter(|d| d.weekday() == weekday)
+            .collect();
+        match ordinal {
+            None => dates.extend(matching),
+            Some(n) => dates.extend(select_positions(&matching, &[n])),
+        }
+    }
+    dates.sort();
+    dates.dedup();
+    dates
+}
+
+/// Picks 1-based positions from `dates`, with negative positions counting
+/// from the end.
+fn select_positions(dates: &[NaiveDate], positions: &[i32]) -> Vec<NaiveDate> {
+    let len = dates.len() as i32;
+    let mut selected: Vec<NaiveDate> = positions
+        .iter()
+        .filter_map(|&pos| {
+            let index = if pos > 0 { pos - 1 } else { len + pos };
+            (0..len).contains(&index).then(|| dates[index as usize])
+        })
+        .collect();
+    selected.sort();
+    selected.dedup();
+    selected
+}
+
+fn error(message: impl Into<String>) -> ParseScheduleError {
+    ParseScheduleError::new(message)
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday, ParseScheduleError> {
+    match s {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        _ => Err(error(format!("unknown weekday \"{}\" in RRULE", s))),
+    }
+}
+
+// Accepts DATE and DATE-TIME values, e.g. 20260106 or 20260106T090000Z
+fn parse_date(value: &str) -> Result<NaiveDate, ParseScheduleError> {
+    value
+        .get(..8)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+        .ok_or_else(|| error(format!("invalid date \"{}\" in recurrence", value)))
+}
+
+fn parse_list<T>(
+    value: &str,
+    parse: impl Fn(&str) -> Result<T, ParseScheduleError>,
+) -> Result<Vec<T>, ParseScheduleError> {
+    value.split(',').map(|item| parse(item.trim())).collect()
+}
+
+fn parse_int(
+    value: &str,
+    name: &str,
+    range: std::ops::RangeInclusive<i32>,
+) -> Result<i32, ParseScheduleError> {
+    value
+        .parse::<i32>()
+        .ok()
+        .filter(|n| *n != 0 && range.contains(&n.abs()))
+        .ok_or_else(|| error(format!("invalid {} value \"{}\"", name, value)))
+}
+
+impl FromStr for Rule {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut frequency = None;
+        let mut rule = Rule {
+            frequency: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+            by_set_pos: Vec::new(),
+            week_start: Weekday::Mon,
+        };
+
+        for part in s.split(';').filter(|p| !p.is_empty()) {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| error(format!("malformed RRULE part \"{}\"", part)))?;
+            match name.to_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(error(format!("unsupported FREQ \"{}\"", value))),
+                    })
+                }
+                "INTERVAL" => {
+                    rule.interval = value
+                        .parse()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| error(format!("invalid INTERVAL \"{}\"", value)))?
+                }
+                "COUNT" => {
+                    rule.count = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|n| *n > 0)
+                            .ok_or_else(|| error(format!("invalid COUNT \"{}\"", value)))?,
+                    )
+                }
+                "UNTIL" => rule.until = Some(parse_date(value)?),
+                "BYDAY" => {
+                    rule.by_day = parse_list(&value.to_uppercase(), |item| {
+                        let split = item.len().saturating_sub(2);
+                        let (ordinal, day) = item
+                            .split_at_checked(split)
+                            .ok_or_else(|| error(format!("invalid BYDAY value \"{}\"", item)))?;
+                        let ordinal = match ordinal {
+                            "" => None,
+                            n => Some(parse_int(
+                                n.trim_start_matches('+'),
+                                "BYDAY ordinal",
+                                1..=53,
+                            )?),
+                        };
+                        Ok((ordinal, parse_weekday(day)?))
+                    })?
+                }
+                "BYMONTHDAY" => {
+                    rule.by_month_day =
+                        parse_list(value, |item| parse_int(item, "BYMONTHDAY", 1..=31))?
+                }
+                "BYMONTH" => {
+                    rule.by_month = parse_list(value, |item| {
+                        item.parse::<u32>()
+                            .ok()
+                            .filter(|m| (1..=12).contains(m))
+                            .ok_or_else(|| error(format!("invalid BYMONTH value \"{}\"", item)))
+                    })?
+                }
+                "BYSETPOS" => {
+                    rule.by_set_pos =
+                        parse_list(value, |item| parse_int(item, "BYSETPOS", 1..=366))?
+                }
+                "WKST" => rule.week_start = parse_weekday(&value.to_uppercase())?,
+                other => return Err(error(format!("unsupported RRULE part \"{}\"", other))),
+            }
+        }
+
+        rule.frequency = frequency.ok_or_else(|| error("RRULE is missing FREQ"))?;
+        if rule.count.is_some() && rule.until.is_some() {
+            return Err(error("RRULE can't have both COUNT and UNTIL"));
+        }
+        if rule.by_day.iter().any(|(ordinal, _)| ordinal.is_some())
+            && !matches!(rule.frequency, Frequency::Monthly | Frequency::Yearly)
+        {
+            return Err(error(
+                "BYDAY ordinals like \"-1FR\" need FREQ=MONTHLY or FREQ=YEARLY",
+            ));
+        }
+        Ok(rule)
+    }
+}
+
+impl FromStr for Recurrence {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut dtstart = None;
+        let mut rule = None;
+
+        for line in s.split_whitespace() {
+            let upper = line.to_uppercase();
+            if upper.starts_with("DTSTART") {
+                let (_, value) = line
+                    .rsplit_once(':')
+                    .ok_or_else(|| error(format!("malformed DTSTART \"{}\"", line)))?;
+                dtstart = Some(parse_date(value)?);
+            } else if let Some(value) = upper.strip_prefix("RRULE:") {
+                rule = Some(value.parse::<Rule>()?);
+            } else {
+                return Err(error(format!(
+                    "unexpected \"{}\" in recurrence (expected DTSTART and RRULE lines)",
+                    line
+                )));
+            }
+        }
+
+        let mut recurrence = Recurrence {
+            source: s.trim().to_string(),
+            dtstart: dtstart.ok_or_else(|| error("recurrence is missing DTSTART"))?,
+            rule: rule.ok_or_else(|| error("recurrence is missing an RRULE"))?,
+        };
+        // COUNT becomes the date of the last occurrence, found once here
+        // rather than by counting from DTSTART on every check
+        if let Some(count) = recurrence.rule.count {
+            let last = recurrence.last_counted(count).ok_or_else(|| {
+                error(format!(
+                    "COUNT={} isn't reached within {} periods of the rule; use UNTIL instead",
+                    count, MAX_COUNTED_PERIODS
+                ))
+            })?;
+            recurrence.rule.until = Some(last);
+        }
+        Ok(recurrence)
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl TryFrom<String> for Recurrence {
+    type Error = ParseScheduleError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Recurrence> for String {
+    fn from(recurrence: Recurrence) -> Self {
+        recurrence.source
+    }
+}
@@ -1,102 +1,608 @@
-use chrono::{DateTime, Local, NaiveTime, Timelike};
+use crate::clock::{Clock, SystemClock};
+use crate::cron::CronSchedule;
+use crate::document::{CURRENT_VERSION, ReminderDocument};
+use crate::error::{ReminderError, ValidationIssue};
+use crate::format::Format;
+use crate::holidays::HolidayCalendar;
+use crate::recurrence::Recurrence;
+use crate::rotation::{RotationKind, RotationStrategy, RoundRobin};
+use crate::schedule::{
+    Period, Priority, ScheduleContext, TimeWindow, TimeWindows, Weekday, WindowMatch,
+};
+use crate::settings::Settings;
+use crate::source::{LoadedFile, Merged, SourceFile, merge_sources, read_sources};
+use crate::state::ReminderState;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::io;
+use std::num::NonZeroU64;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Reminder {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>, // stable handle; derived from the content when absent
     pub text: String,
     pub category: String,
-    pub priority: String,
-    pub time_range: Option<String>, // e.g., "09:00-17:00" or "morning"
-    pub days: Option<Vec<String>>,  // e.g., ["monday", "tuesday", "wednesday"]
+    pub priority: Priority,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_range: Option<TimeWindows>, // e.g., "09:00-17:00", "morning" or a list of them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub days: Option<Vec<Weekday>>, // e.g., ["monday", "tuesday", "wednesday"]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>, // a one-off reminder, e.g., "2026-11-03"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<NaiveDate>, // first day shown, inclusive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<NaiveDate>, // last day shown, inclusive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<Recurrence>, // e.g., "DTSTART:20260106 RRULE:FREQ=MONTHLY;BYDAY=-1FR"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cron: Option<CronSchedule>, // e.g., "0 9 * * 1-5", instead of time_range and days
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<Tz>, // IANA name, e.g., "Europe/Berlin"
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip_holidays: bool, // hidden on days in the holiday calendar
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub only_on_holidays: bool, // shown only on days in the holiday calendar
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<u64>, // time on screen, instead of the rotation interval
+    #[serde(skip)]
+    pub source: Option<PathBuf>, // the file it was read from; not part of its ID
 }
 
 impl Reminder {
     pub fn get_color(&self) -> egui::Color32 {
-        match self.priority.to_lowercase().as_str() {
-            "high" | "urgent" => egui::Color32::from_rgb(255, 100, 100),
-            "medium" | "important" => egui::Color32::from_rgb(255, 200, 100),
-            "low" | "info" => egui::Color32::from_rgb(100, 200, 255),
-            _ => egui::Color32::WHITE,
+        match self.priority {
+            Priority::Urgent | Priority::High => egui::Color32::from_rgb(255, 100, 100),
+            Priority::Medium => egui::Color32::from_rgb(255, 200, 100),
+            Priority::Low => egui::Color32::from_rgb(100, 200, 255),
         }
     }
 
     pub fn is_active_now(&self) -> bool {
-        let now = Local::now();
+        self.is_active_at(&Local::now())
+    }
 
-        // Check day of week if specified
-        if let Some(days) = &self.days {
-            let current_day = now.format("%A").to_string().to_lowercase();
-            if !days.iter().any(|d| d.to_lowercase() == current_day) {
-                return false;
-            }
+    /// Whether the reminder should be shown at the given instant. Unless the
+    /// reminder has its own `timezone`, it's evaluated against the wall-clock
+    /// time of `now`'s time zone.
+    pub fn is_active_at<Z: TimeZone>(&self, now: &DateTime<Z>) -> bool {
+        self.is_active_in(now, &ScheduleContext::default())
+    }
+
+    /// Like [`Reminder::is_active_at`], with defaults from `context` for
+    /// anything the reminder doesn't set itself.
+    pub fn is_active_in<Z: TimeZone>(&self, now: &DateTime<Z>, context: &ScheduleContext) -> bool {
+        // Time windows, days and dates are all wall-clock values in the
+        // reminder's zone; converting the instant handles DST transitions
+        match self.timezone.or(context.default_timezone) {
+            Some(zone) => self.is_active_local(&now.with_timezone(&zone), context),
+            None => self.is_active_local(now, context),
+        }
+    }
+
+    fn is_active_local<Z: TimeZone>(&self, now: &DateTime<Z>, context: &ScheduleContext) -> bool {
+        // Shown for a while after each firing; date filters apply to the firing day
+        if let Some(cron) = &self.cron {
+            return cron
+                .recent_fires(now)
+                .any(|fired| self.is_on_day(fired.date_naive(), context));
         }
 
+        let today = now.date_naive();
+
         // Check time range if specified
         if let Some(time_range) = &self.time_range {
-            return self.is_in_time_range(time_range, now);
+            return time_range
+                .iter()
+                .filter_map(|window| context.resolve(window))
+                .any(|window| match window.matches(now.time()) {
+                    WindowMatch::Outside => false,
+                    WindowMatch::SameDay => self.is_on_day(today, context),
+                    // Day and date filters apply to the day the window started
+                    WindowMatch::StartedYesterday => {
+                        today.pred_opt().is_some_and(|d| self.is_on_day(d, context))
+                    }
+                });
         }
 
-        true
+        self.is_on_day(today, context)
     }
 
-    fn is_in_time_range(&self, time_range: &str, now: DateTime<Local>) -> bool {
-        match time_range.to_lowercase().as_str() {
-            "morning" => {
-                let hour = now.hour();
-                hour >= 6 && hour < 12
-            }
-            "afternoon" => {
-                let hour = now.hour();
-                hour >= 12 && hour < 17
-            }
-            "evening" => {
-                let hour = now.hour();
-                hour >= 17 && hour < 22
+    // Check date bounds, holidays, recurrence and day of week if specified
+    fn is_on_day(&self, day: NaiveDate, context: &ScheduleContext) -> bool {
+        if self.date.is_some_and(|date| date != day)
+            || self.start_date.is_some_and(|start| day < start)
+            || self.end_date.is_some_and(|end| day > end)
+        {
+            return false;
+        }
+
+        if self.skip_holidays || self.only_on_holidays {
+            // Without a calendar no day is a holiday
+            let holiday = context.holidays.as_ref().is_some_and(|h| h.is_holiday(day));
+            if holiday == self.skip_holidays {
+                return false;
             }
-            _ => {
-                // Parse "HH:MM-HH:MM" format
-                if let Some((start_str, end_str)) = time_range.split_once('-') {
-                    if let (Ok(start), Ok(end)) = (
-                        NaiveTime::parse_from_str(start_str.trim(), "%H:%M"),
-                        NaiveTime::parse_from_str(end_str.trim(), "%H:%M"),
-                    ) {
-                        let current_time = now.time();
-                        return current_time >= start && current_time <= end;
+        }
+
+        if let Some(recurrence) = &self.recurrence
+            && !recurrence.occurs_on(day)
+        {
+            return false;
+        }
+
+        match &self.days {
+            Some(days) => days.contains(&Weekday::from(day.weekday())),
+            None => true,
+        }
+    }
+
+    /// When the occurrence of the reminder that's active at `now` ends: the
+    /// end of its time window, of its cron duration, or of the day. `None`
+    /// if the reminder isn't active at `now`.
+    pub fn occurrence_end_in<Z: TimeZone>(
+        &self,
+        now: &DateTime<Z>,
+        context: &ScheduleContext,
+    ) -> Option<DateTime<Utc>> {
+        if !self.is_active_in(now, context) {
+            return None;
+        }
+        match self.timezone.or(context.default_timezone) {
+            Some(zone) => self.occurrence_end_local(&now.with_timezone(&zone), context),
+            None => self.occurrence_end_local(now, context),
+        }
+    }
+
+    fn occurrence_end_local<Z: TimeZone>(
+        &self,
+        now: &DateTime<Z>,
+        context: &ScheduleContext,
+    ) -> Option<DateTime<Utc>> {
+        if let Some(cron) = &self.cron {
+            let duration = Duration::minutes(i64::from(cron.duration_mins));
+            return cron
+                .recent_fires(now)
+                .find(|fired| self.is_on_day(fired.date_naive(), context))
+                .map(|fired| (fired + duration).with_timezone(&Utc));
+        }
+
+        let today = now.date_naive();
+        let end = match &self.time_range {
+            // Overlapping windows: the occurrence lasts until the last one ends
+            Some(time_range) => time_range
+                .iter()
+                .filter_map(|window| context.resolve(window))
+                .filter_map(|window| {
+                    match window.matches(now.time()) {
+                        WindowMatch::Outside => None,
+                        WindowMatch::SameDay => Some(today),
+                        WindowMatch::StartedYesterday => today.pred_opt(),
                     }
+                    .filter(|&started| self.is_on_day(started, context))
+                    .map(|started| window.ends_at(started))
+                })
+                .max()?,
+            None => today.succ_opt()?.and_hms_opt(0, 0, 0)?,
+        };
+
+        // An end inside a DST gap falls back to the first valid time after it
+        let zone = now.timezone();
+        zone.from_local_datetime(&end)
+            .earliest()
+            .or_else(|| {
+                zone.from_local_datetime(&(end + Duration::hours(1)))
+                    .earliest()
+            })
+            .map(|end| end.with_timezone(&Utc))
+    }
+
+    /// The reminder's `id`, or when it has none, one derived from its
+    /// content. A derived ID stays the same as long as the content does,
+    /// across reloads and restarts.
+    pub fn id(&self) -> String {
+        if let Some(id) = &self.id {
+            return id.clone();
+        }
+        let content = serde_json::to_string(self).unwrap_or_default();
+        format!("{:016x}", fnv1a(content.as_bytes()))
+    }
+
+    /// Checks for mistakes the field types can't rule out. Returns one
+    /// message per problem found.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if self.id.as_ref().is_some_and(|id| id.trim().is_empty()) {
+            problems.push("id is empty".to_string());
+        }
+
+        if self.text.trim().is_empty() {
+            problems.push("text is empty".to_string());
+        }
+
+        if let Some(time_range) = &self.time_range
+            && time_range.is_empty()
+        {
+            problems
+                .push("time_range is an empty list, so the reminder would never show".to_string());
+        }
+
+        if let Some(days) = &self.days
+            && days.is_empty()
+        {
+            problems.push("days is an empty list, so the reminder would never show".to_string());
+        }
+
+        if self.cron.is_some() && (self.time_range.is_some() || self.days.is_some()) {
+            problems.push("cron can't be combined with time_range or days".to_string());
+        }
+
+        if self.date.is_some() && (self.start_date.is_some() || self.end_date.is_some()) {
+            problems.push("date can't be combined with start_date or end_date".to_string());
+        }
+
+        if self.duration_secs == Some(0) {
+            problems.push("duration_secs must be at least 1".to_string());
+        }
+
+        if self.skip_holidays && self.only_on_holidays {
+            problems.push("skip_holidays and only_on_holidays can't both be set".to_string());
+        }
+
+        if let (Some(start), Some(end)) = (self.start_date, self.end_date)
+            && start > end
+        {
+            problems.push(format!("start_date {} is after end_date {}", start, end));
+        }
+
+        problems
+    }
+}
+
+/// Parses the content of a reminders file, in the [`Format`] the file's
+/// extension selects: either a versioned [`ReminderDocument`] or, from
+/// version 1, a bare array of reminder objects.
+///
+/// Every entry is checked, so the error lists all invalid reminders rather
+/// than just the first one.
+pub fn parse_document(path: &Path, content: &str) -> Result<ReminderDocument, ReminderError> {
+    parse_entries(path, content, false)
+}
+
+/// The reminders in a reminders file. See [`parse_document`].
+pub fn parse_reminders(path: &Path, content: &str) -> Result<Vec<Reminder>, ReminderError> {
+    parse_document(path, content).map(|document| document.reminders)
+}
+
+/// Like [`parse_reminders`], but also rejects what the display tolerates:
+/// fields it doesn't know, which are usually misspelled, and reminders that
+/// appear more than once.
+pub fn lint_reminders(path: &Path, content: &str) -> Result<Vec<Reminder>, ReminderError> {
+    parse_entries(path, content, true).map(|document| document.reminders)
+}
+
+/// A version 1 reminders file rewritten at [`CURRENT_VERSION`], keeping its
+/// text and comments, or `None` if it's already current. Fails if the file
+/// doesn't parse, or its layout can't be kept.
+pub fn migrate_document(path: &Path, content: &str) -> Result<Option<String>, ReminderError> {
+    let document = parse_document(path, content)?;
+    if !document.is_legacy() {
+        return Ok(None);
+    }
+
+    let format = Format::from_path(path);
+    let migrated = format.upgrade_legacy(content);
+    let same = parse_document(path, &migrated).is_ok_and(|upgraded| {
+        !upgraded.is_legacy()
+            && serde_json::to_value(&upgraded.reminders).ok()
+                == serde_json::to_value(&document.reminders).ok()
+    });
+    if same {
+        return Ok(Some(migrated));
+    }
+    if format.has_comments(content) {
+        return Err(ReminderError::io(
+            path,
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!(
+                    "can't migrate this {} file without losing its comments; edit it by hand",
+                    format
+                ),
+            ),
+        ));
+    }
+    format
+        .serialize(
+            path,
+            &ReminderDocument::new(Settings::default(), document.reminders),
+        )
+        .map(Some)
+}
+
+// Every field a reminder object can have
+const REMINDER_FIELDS: [&str; 15] = [
+    "id",
+    "text",
+    "category",
+    "priority",
+    "time_range",
+    "days",
+    "date",
+    "start_date",
+    "end_date",
+    "recurrence",
+    "cron",
+    "timezone",
+    "skip_holidays",
+    "only_on_holidays",
+    "duration_secs",
+];
+
+fn parse_entries(
+    path: &Path,
+    content: &str,
+    strict: bool,
+) -> Result<ReminderDocument, ReminderError> {
+    let document = Format::from_path(path).parse_document(path, content)?;
+    let periods = document.settings.periods.clone();
+    check_entries(path, document, strict, &periods)
+}
+
+// Turns a file's entries into reminders, read from `path`. `periods` are the
+// names a time_range can use, which for a file among others includes theirs.
+pub(crate) fn check_entries(
+    path: &Path,
+    document: ReminderDocument<serde_json::Value>,
+    strict: bool,
+    periods: &BTreeMap<String, TimeWindow>,
+) -> Result<ReminderDocument, ReminderError> {
+    let ReminderDocument {
+        version,
+        enabled,
+        include,
+        settings,
+        reminders: entries,
+    } = document;
+
+    let mut reminders = Vec::with_capacity(entries.len());
+    let mut issues: Vec<ValidationIssue> = settings
+        .validate()
+        .into_iter()
+        .map(|message| ValidationIssue {
+            index: None,
+            text: None,
+            message,
+        })
+        .collect();
+    let mut seen_ids: HashMap<String, usize> = HashMap::new();
+    for (index, entry) in entries.into_iter().enumerate() {
+        let text = entry
+            .get("text")
+            .and_then(|t| t.as_str())
+            .map(|t| t.to_string());
+
+        let mut problems = Vec::new();
+        if strict && let Some(fields) = entry.as_object() {
+            problems.extend(
+                fields
+                    .keys()
+                    .filter(|key| !REMINDER_FIELDS.contains(&key.as_str()))
+                    .map(|key| format!("unknown field \"{}\"", key)),
+            );
+        }
+
+        match serde_json::from_value::<Reminder>(entry.clone()) {
+            Ok(mut reminder) => {
+                reminder.source = Some(path.to_path_buf());
+                problems.extend(reminder.validate());
+                problems.extend(undefined_periods(&reminder, periods));
+                // Without an explicit id, identical reminders share a derived one
+                let id = match &reminder.id {
+                    Some(id) => Some(id.clone()),
+                    None if strict => Some(reminder.id()),
+                    None => None,
+                };
+                if let Some(id) = id
+                    && let first = *seen_ids.entry(id.clone()).or_insert(index)
+                    && first != index
+                {
+                    problems.push(match reminder.id {
+                        Some(_) => {
+                            format!("id \"{}\" is already used by reminder #{}", id, first + 1)
+                        }
+                        None => format!("duplicates reminder #{}", first + 1),
+                    });
+                }
+                if problems.is_empty() {
+                    reminders.push(reminder);
                 }
-                true // Default to always active if can't parse
             }
+            Err(e) => problems.extend(field_errors(&entry, e)),
+        }
+        issues.extend(problems.into_iter().map(|message| ValidationIssue {
+            index: Some(index),
+            text: text.clone(),
+            message,
+        }));
+    }
+
+    if issues.is_empty() {
+        Ok(ReminderDocument {
+            version,
+            enabled,
+            include,
+            settings,
+            reminders,
+        })
+    } else {
+        Err(ReminderError::Invalid {
+            path: path.to_path_buf(),
+            issues,
+        })
+    }
+}
+
+// A name that isn't a period is most likely a typo, which would otherwise
+// hide the reminder for good
+fn undefined_periods(reminder: &Reminder, periods: &BTreeMap<String, TimeWindow>) -> Vec<String> {
+    reminder
+        .time_range
+        .iter()
+        .flatten()
+        .filter_map(|window| match window {
+            TimeWindow::Named(name) if !periods.contains_key(name) => Some(format!(
+                "time_range: no period named \"{}\" (define it under settings.periods)",
+                name
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+// serde stops at the first bad field of an entry; trying each field on its
+// own reports all of them, each with the field's name
+fn field_errors(entry: &serde_json::Value, error: serde_json::Error) -> Vec<String> {
+    let Some(fields) = entry.as_object() else {
+        return vec![error.to_string()];
+    };
+
+    let mut problems: Vec<String> = ["text", "category", "priority"]
+        .into_iter()
+        .filter(|field| !fields.contains_key(*field))
+        .map(|field| format!("missing field `{}`", field))
+        .collect();
+    for (field, value) in fields {
+        let mut probe = serde_json::json!({"text": "", "category": "", "priority": "low"});
+        probe[field] = value.clone();
+        if let Err(e) = serde_json::from_value::<Reminder>(probe) {
+            problems.push(format!("{}: {}", field, e));
         }
     }
+
+    if problems.is_empty() {
+        problems.push(error.to_string());
+    }
+    problems
+}
+
+// serde_json appends " at line X column Y", which the Parse error already carries
+pub(crate) fn strip_position(error: &serde_json::Error) -> String {
+    let message = error.to_string();
+    let suffix = format!(" at line {} column {}", error.line(), error.column());
+    message
+        .strip_suffix(&suffix)
+        .map(|m| m.to_string())
+        .unwrap_or(message)
 }
 
+/// Seconds each reminder stays on screen when nothing else sets it.
+pub const DEFAULT_ROTATION_INTERVAL: u64 = 30;
+
 pub struct ReminderManager {
     reminders: Vec<Reminder>,
-    current_index: usize,
+    // Each reminder's `id()`, computed once per load as deriving one
+    // serializes the reminder
+    ids: Vec<String>,
+    // The reminder on screen, by identity, so it stays put when others
+    // become active or inactive or the file is reloaded
+    current_key: Option<String>,
+    // Its position in `reminders`, to continue from when it goes away
+    current_position: usize,
     last_rotation: u64,
-    rotation_interval: u64, // seconds
+    // Seconds per reminder, if set by the caller rather than the file
+    rotation_interval: Option<u64>,
+    // The settings of the files, as last loaded and merged
+    settings: Settings,
+    // Set by the caller, so the file's setting doesn't apply
+    default_timezone: Option<Tz>,
+    rotation_pinned: bool,
+    // The file's rotation setting that `rotation` was made from
+    rotation_kind: Option<RotationKind>,
     last_file_check: String,
+    // What was last read, even if it failed to load, and what loaded
+    last_read: Option<Vec<SourceFile>>,
+    files: Vec<LoadedFile>,
+    // Whether `file_path` is a directory of reminders files
+    directory: bool,
+    // Whether the next load creates a missing file with the defaults, which
+    // is only the first load of a file found by `find_reminders_file`
+    create_missing: bool,
+    last_error: Option<ReminderError>,
     file_path: String,
+    clock: Arc<dyn Clock>,
+    context: ScheduleContext,
+    rotation: Box<dyn RotationStrategy>,
+    state: ReminderState,
+    state_path: PathBuf,
+}
+
+impl Default for ReminderManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ReminderManager {
     pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    /// Uses the file from [`ReminderManager::find_reminders_file`], which is
+    /// created with the default reminders if it doesn't exist yet.
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self::build(Self::find_reminders_file(), clock, true)
+    }
+
+    /// Uses the given reminders file or directory. If it doesn't exist that's
+    /// reported as an error like any other, rather than creating it.
+    pub fn with_file(file_path: impl Into<String>, clock: Arc<dyn Clock>) -> Self {
+        Self::build(file_path.into(), clock, false)
+    }
+
+    fn build(file_path: String, clock: Arc<dyn Clock>, create_missing: bool) -> Self {
+        let state_path = ReminderState::path_for(Path::new(&file_path));
+        // A damaged state file only loses acknowledgements, so start afresh
+        let state = ReminderState::load(&state_path).unwrap_or_default();
         let mut manager = Self {
             reminders: Vec::new(),
-            current_index: 0,
-            last_rotation: Self::current_timestamp(),
-            rotation_interval: 30, // 30 seconds between reminders
+            ids: Vec::new(),
+            current_key: None,
+            current_position: 0,
+            last_rotation: timestamp(clock.as_ref()),
+            rotation_interval: None,
+            settings: Settings::default(),
+            default_timezone: None,
+            rotation_pinned: false,
+            rotation_kind: None,
             last_file_check: String::new(),
-            file_path: Self::find_reminders_file(),
+            last_read: None,
+            files: Vec::new(),
+            directory: false,
+            create_missing,
+            last_error: None,
+            file_path,
+            clock,
+            context: ScheduleContext::default(),
+            rotation: Box::new(RoundRobin),
+            state,
+            state_path,
         };
-        manager.load_reminders();
+        // Any failure is kept in `last_error` for the UI to show
+        let _ = manager.load_reminders();
         manager
     }
 
-    fn find_reminders_file() -> String {
+    /// The reminders file used when none is given: `REMINDERS_FILE`, or the
+    /// first `work_reminders.json` found in the current or home directory.
+    pub fn find_reminders_file() -> String {
         // Check environment variable first
         if let Ok(path) = std::env::var("REMINDERS_FILE") {
             return path;
@@ -123,119 +629,499 @@ impl ReminderManager {
         "work_reminders.json".to_string()
     }
 
-    pub fn load_reminders(&mut self) {
-        match fs::read_to_string(&self.file_path) {
-            Ok(content) => {
-                match serde_json::from_str::<Vec<Reminder>>(&content) {
-                    Ok(reminders) => {
-                        self.reminders = reminders;
-                        self.last_file_check = Local::now().format("%H:%M:%S").to_string();
+    pub fn file_path(&self) -> &Path {
+        Path::new(&self.file_path)
+    }
 
-                        // Reset index if we have fewer reminders now
-                        if self.current_index >= self.reminders.len() && !self.reminders.is_empty()
-                        {
-                            self.current_index = 0;
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Error parsing reminders file: {}", e);
-                    }
-                }
+    /// The files the reminders were last read from, including any reached
+    /// through `include`.
+    pub fn source_paths(&self) -> Vec<&Path> {
+        self.last_read
+            .iter()
+            .flatten()
+            .map(|file| file.path.as_path())
+            .collect()
+    }
+
+    /// Loads the reminders file, or every reminders file in the directory if
+    /// it's a directory. A missing file is an error, except on the first load
+    /// of one found by [`ReminderManager::find_reminders_file`], which
+    /// creates it with the defaults. On failure the previously loaded
+    /// reminders are kept and the error is also available from
+    /// [`ReminderManager::last_error`].
+    pub fn load_reminders(&mut self) -> Result<(), ReminderError> {
+        let create_missing = std::mem::take(&mut self.create_missing);
+        let result = match fs::metadata(&self.file_path) {
+            Ok(metadata) => {
+                self.directory = metadata.is_dir();
+                read_sources(Path::new(&self.file_path)).and_then(|files| self.apply_files(files))
             }
-            Err(_) => {
-                // Create default file if it doesn't exist
-                self.create_default_reminders_file();
+            Err(e) if e.kind() == io::ErrorKind::NotFound && create_missing => {
+                self.create_defaults()
+            }
+            Err(e) => Err(ReminderError::io(&self.file_path, e)),
+        };
+        self.last_error = result.as_ref().err().cloned();
+        result
+    }
+
+    fn apply_files(&mut self, files: Vec<SourceFile>) -> Result<(), ReminderError> {
+        let result = merge_sources(&files);
+        // Remember the content even if it failed to parse, so the same broken
+        // file isn't re-parsed (and re-reported) on every change notification.
+        self.last_read = Some(files);
+
+        self.apply_merged(result?);
+        Ok(())
+    }
+
+    fn apply_merged(&mut self, merged: Merged) {
+        self.ids = merged.reminders.iter().map(Reminder::id).collect();
+        self.reminders = merged.reminders;
+        self.settings = merged.settings;
+        self.files = merged.files;
+        self.apply_settings();
+        self.last_file_check = self.clock.now().format("%H:%M:%S").to_string();
+    }
+
+    // The file's settings fill in whatever the caller hasn't set
+    fn apply_settings(&mut self) {
+        self.context.default_timezone = self.default_timezone.or(self.settings.timezone);
+        self.context.periods = Arc::new(self.settings.periods.clone());
+        if !self.rotation_pinned {
+            let kind = self.settings.rotation.unwrap_or_default();
+            // Replacing it unchanged would lose a strategy's own state
+            if self.rotation_kind != Some(kind) {
+                self.rotation = kind.create();
+                self.rotation_kind = Some(kind);
             }
         }
     }
 
-    fn create_default_reminders_file(&mut self) {
+    /// Writes the default reminders to the reminders file, in the format its
+    /// extension names, and loads them. Fails if the file already exists.
+    pub fn create_defaults(&mut self) -> Result<(), ReminderError> {
+        let path = PathBuf::from(&self.file_path);
+        if path.exists() {
+            return Err(ReminderError::io(
+                &path,
+                io::Error::from(io::ErrorKind::AlreadyExists),
+            ));
+        }
         let default_reminders = vec![
             Reminder {
                 text: "Check your monitoring dashboards".to_string(),
                 category: "DevOps".to_string(),
-                priority: "high".to_string(),
-                time_range: Some("09:00-17:00".to_string()),
-                days: Some(vec![
-                    "monday".to_string(),
-                    "tuesday".to_string(),
-                    "wednesday".to_string(),
-                    "thursday".to_string(),
-                    "friday".to_string(),
-                ]),
+                priority: Priority::High,
+                time_range: Some("09:00-17:00".parse().expect("valid time range")),
+                days: Some(Weekday::WORKDAYS.to_vec()),
+                skip_holidays: true,
+                ..Default::default()
             },
             Reminder {
                 text: "Review and respond to alerts".to_string(),
                 category: "DevOps".to_string(),
-                priority: "high".to_string(),
-                time_range: Some("09:00-17:00".to_string()),
-                days: Some(vec![
-                    "monday".to_string(),
-                    "tuesday".to_string(),
-                    "wednesday".to_string(),
-                    "thursday".to_string(),
-                    "friday".to_string(),
-                ]),
+                priority: Priority::High,
+                time_range: Some("09:00-17:00".parse().expect("valid time range")),
+                days: Some(Weekday::WORKDAYS.to_vec()),
+                skip_holidays: true,
+                ..Default::default()
             },
             Reminder {
                 text: "Take a 5-minute break and stretch".to_string(),
                 category: "Health".to_string(),
-                priority: "medium".to_string(),
+                priority: Priority::Medium,
                 time_range: None,
                 days: None,
+                ..Default::default()
             },
             Reminder {
                 text: "Check backup status and logs".to_string(),
                 category: "DevOps".to_string(),
-                priority: "medium".to_string(),
-                time_range: Some("morning".to_string()),
-                days: Some(vec![
-                    "monday".to_string(),
-                    "wednesday".to_string(),
-                    "friday".to_string(),
-                ]),
+                priority: Priority::Medium,
+                time_range: Some(TimeWindow::Period(Period::Morning).into()),
+                days: Some(vec![Weekday::Monday, Weekday::Wednesday, Weekday::Friday]),
+                skip_holidays: true,
+                ..Default::default()
             },
             Reminder {
                 text: "Review security alerts and patches".to_string(),
                 category: "Security".to_string(),
-                priority: "high".to_string(),
-                time_range: Some("morning".to_string()),
-                days: Some(vec!["monday".to_string(), "thursday".to_string()]),
+                priority: Priority::High,
+                time_range: Some(TimeWindow::Period(Period::Morning).into()),
+                days: Some(vec![Weekday::Monday, Weekday::Thursday]),
+                skip_holidays: true,
+                ..Default::default()
             },
         ];
 
-        if let Ok(json) = serde_json::to_string_pretty(&default_reminders) {
-            if fs::write(&self.file_path, json).is_ok() {
-                self.reminders = default_reminders;
-                self.last_file_check = Local::now().format("%H:%M:%S").to_string();
+        let document = ReminderDocument::new(Settings::default(), default_reminders);
+        let content = Format::from_path(&path).serialize(&path, &document)?;
+        let result = self.write_content(path, content);
+        self.last_error = result.as_ref().err().cloned();
+        result
+    }
+
+    /// All loaded reminders, in file order, whether or not they're active.
+    pub fn list(&self) -> &[Reminder] {
+        &self.reminders
+    }
+
+    /// The reminder with this ID (explicit or derived), if loaded.
+    pub fn get(&self, id: &str) -> Option<&Reminder> {
+        self.position(id).map(|p| &self.reminders[p])
+    }
+
+    fn position(&self, id: &str) -> Option<usize> {
+        self.ids.iter().position(|other| other == id)
+    }
+
+    /// The reminders the display rotates through right now: those active and
+    /// neither acknowledged nor snoozed, in file order.
+    pub fn active_reminders(&self) -> Vec<&Reminder> {
+        self.active_positions()
+            .into_iter()
+            .map(|p| &self.reminders[p])
+            .collect()
+    }
+
+    /// Replaces the reminder with the same ID, or adds it at the end if
+    /// there's none, and saves the file. A reminder without an explicit `id`
+    /// gets a new derived one when its content changes, so it's added as a
+    /// new reminder; use [`ReminderManager::replace`] to edit one.
+    pub fn upsert(&mut self, reminder: Reminder) -> Result<(), ReminderError> {
+        self.ensure_file_loaded()?;
+        let index = self
+            .position(&reminder.id())
+            .unwrap_or(self.reminders.len());
+        self.write_entry(index, &reminder)
+    }
+
+    /// Replaces the reminder with ID `id` by `reminder`, in the same place,
+    /// and saves the file. When the ID changes, as a derived one does with
+    /// the content, the reminder's acknowledgement and snooze move to the new
+    /// ID. Returns `false` if no reminder has this ID.
+    pub fn replace(&mut self, id: &str, reminder: Reminder) -> Result<bool, ReminderError> {
+        self.ensure_file_loaded()?;
+        let Some(index) = self.position(id) else {
+            return Ok(false);
+        };
+        self.write_entry(index, &reminder)?;
+
+        let new_id = reminder.id();
+        if new_id != id {
+            self.state.rename(id, &new_id);
+            self.state.save(&self.state_path)?;
+        }
+        Ok(true)
+    }
+
+    // Checks `reminder`, then writes it at `index`, past the end to add it
+    fn write_entry(&mut self, index: usize, reminder: &Reminder) -> Result<(), ReminderError> {
+        let issues: Vec<ValidationIssue> = reminder
+            .validate()
+            .into_iter()
+            .map(|message| ValidationIssue {
+                index: Some(index),
+                text: Some(reminder.text.clone()),
+                message,
+            })
+            .collect();
+        if !issues.is_empty() {
+            return Err(ReminderError::Invalid {
+                path: PathBuf::from(&self.file_path),
+                issues,
+            });
+        }
+
+        let (path, content) = self.edited_content(index, Some(reminder))?;
+        self.write_content(path, content)
+    }
+
+    /// Removes the reminder with this ID and saves the file. Returns the
+    /// removed reminder, or `None` if there was no such reminder.
+    pub fn remove(&mut self, id: &str) -> Result<Option<Reminder>, ReminderError> {
+        self.ensure_file_loaded()?;
+        let Some(index) = self.position(id) else {
+            return Ok(None);
+        };
+        let removed = self.reminders[index].clone();
+        let (path, content) = self.edited_content(index, None)?;
+        self.write_content(path, content)?;
+        Ok(Some(removed))
+    }
+
+    // The loaded list is stale while the file is broken; writing it back
+    // would throw away whatever the user has in the file
+    fn ensure_file_loaded(&self) -> Result<(), ReminderError> {
+        match &self.last_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    // The file the reminder at `index` came from, and its content with that
+    // reminder replaced by `reminder`, or removed when it's `None`; `index`
+    // past the end appends to the reminders file. Only that entry's text
+    // changes where the format allows, so the rest of the file keeps its
+    // layout and comments.
+    fn edited_content(
+        &self,
+        index: usize,
+        reminder: Option<&Reminder>,
+    ) -> Result<(PathBuf, String), ReminderError> {
+        let file = match self.files.iter().find(|file| file.range.contains(&index)) {
+            Some(file) => file,
+            // Which of a directory's files a new reminder belongs in is up
+            // to the user
+            None if self.directory => {
+                return Err(ReminderError::io(
+                    &self.file_path,
+                    io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        "this is a directory; add the reminder to one of its files with --file",
+                    ),
+                ));
+            }
+            None => self.files.last().ok_or_else(|| {
+                ReminderError::io(&self.file_path, io::Error::from(io::ErrorKind::NotFound))
+            })?,
+        };
+        let path = file.path.as_path();
+        let local = index.min(file.range.end) - file.range.start;
+        let content = self
+            .last_read
+            .iter()
+            .flatten()
+            .find(|read| read.path == file.path)
+            .map(|read| read.content.as_str());
+
+        let format = Format::from_path(path);
+        if let Some(content) = content {
+            if let Some(edited) = format.splice_entry(content, local, reminder) {
+                return Ok((path.to_path_buf(), edited));
+            }
+            // Rewriting the file from the reminders would drop its comments
+            if format.has_comments(content) {
+                return Err(ReminderError::io(
+                    path,
+                    io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        format!(
+                            "can't change this {} file without losing its comments; edit it by hand",
+                            format
+                        ),
+                    ),
+                ));
+            }
+        }
+
+        let mut document = ReminderDocument {
+            version: file.version,
+            enabled: true,
+            include: file.include.clone(),
+            settings: file.settings.clone(),
+            reminders: self.reminders[file.range.clone()].to_vec(),
+        };
+        let reminders = &mut document.reminders;
+        match reminder {
+            Some(reminder) if local < reminders.len() => reminders[local] = reminder.clone(),
+            Some(reminder) => reminders.push(reminder.clone()),
+            None => {
+                reminders.remove(local);
             }
         }
+        Ok((path.to_path_buf(), format.serialize(path, &document)?))
     }
 
-    pub fn check_for_updates(&mut self) {
-        self.load_reminders();
+    // Checks the files with `path` changed to `content` as a reload would,
+    // then writes it and makes the result the loaded list. Nothing is
+    // written if it doesn't load.
+    fn write_content(&mut self, path: PathBuf, content: String) -> Result<(), ReminderError> {
+        let mut files = self.last_read.clone().unwrap_or_default();
+        match files.iter_mut().find(|file| file.path == path) {
+            Some(file) => file.content = content.clone(),
+            None => files.push(SourceFile {
+                path: path.clone(),
+                content: content.clone(),
+            }),
+        }
+        let merged = merge_sources(&files)?;
+        fs::write(&path, &content).map_err(|e| ReminderError::io(&path, e))?;
+
+        self.apply_merged(merged);
+        self.last_read = Some(files);
+        Ok(())
     }
 
-    pub fn get_current_reminder(&self) -> Option<&Reminder> {
-        let active_reminders: Vec<&Reminder> = self
-            .reminders
+    /// Reloads the reminders if any of their files differ from what was last
+    /// loaded, or a directory's files were added or removed. Returns `true`
+    /// when a reload happened.
+    pub fn check_for_updates(&mut self) -> bool {
+        match read_sources(Path::new(&self.file_path)) {
+            Ok(files) if self.last_read.as_ref() == Some(&files) => false,
+            Ok(files) => {
+                let result = self.apply_files(files);
+                self.last_error = result.err();
+                true
+            }
+            Err(_) => {
+                let _ = self.load_reminders();
+                true
+            }
+        }
+    }
+
+    /// The settings from the reminders file, as last loaded. Those the
+    /// manager uses are already applied, unless set through its setters.
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// The version of the reminders file, as last loaded; below
+    /// [`CURRENT_VERSION`] for a file that needs migrating. For a directory,
+    /// the lowest of its files'.
+    pub fn file_version(&self) -> u32 {
+        self.files
             .iter()
-            .filter(|r| r.is_active_now())
-            .collect();
+            .map(|file| file.version)
+            .min()
+            .unwrap_or(CURRENT_VERSION)
+    }
 
-        if active_reminders.is_empty() {
-            return None;
+    /// Sets the zone used for reminders that don't specify their own
+    /// `timezone`, over the file's setting. `None` uses the file's setting,
+    /// or else the clock's local time.
+    pub fn set_default_timezone(&mut self, timezone: Option<Tz>) {
+        self.default_timezone = timezone;
+        self.apply_settings();
+    }
+
+    /// Sets the calendar that `skip_holidays` and `only_on_holidays` use.
+    pub fn set_holidays(&mut self, holidays: Option<HolidayCalendar>) {
+        self.context.holidays = holidays.map(Arc::new);
+    }
+
+    /// Sets how many seconds each reminder stays on screen, unless it sets
+    /// its own `duration_secs`, over the file's `interval_secs` setting.
+    pub fn set_rotation_interval(&mut self, seconds: u64) {
+        self.rotation_interval = Some(seconds);
+    }
+
+    /// Seconds each reminder stays on screen unless it sets its own
+    /// `duration_secs`: as set, from the file's settings, or 30.
+    pub fn rotation_interval(&self) -> u64 {
+        self.rotation_interval
+            .or(self.settings.interval_secs.map(NonZeroU64::get))
+            .unwrap_or(DEFAULT_ROTATION_INTERVAL)
+    }
+
+    /// Sets how the next reminder is picked at each rotation, over the
+    /// file's `rotation` setting.
+    pub fn set_rotation_strategy(&mut self, rotation: Box<dyn RotationStrategy>) {
+        self.rotation = rotation;
+        self.rotation_pinned = true;
+    }
+
+    /// Hides the reminder until its current occurrence ends (the end of its
+    /// time window, of its cron duration, or of the day), and records that in
+    /// the state file. Returns `false` if no active reminder has this ID.
+    pub fn acknowledge(&mut self, id: &str) -> Result<bool, ReminderError> {
+        let now = self.clock.now();
+        let Some(until) = (0..self.reminders.len())
+            .filter(|&p| self.ids[p] == id)
+            .find_map(|p| self.reminders[p].occurrence_end_in(&now, &self.context))
+        else {
+            return Ok(false);
+        };
+
+        self.state.prune(now.with_timezone(&Utc));
+        self.state.acknowledge(id, until);
+        self.state.save(&self.state_path)?;
+        Ok(true)
+    }
+
+    /// Acknowledges the reminder on screen. See [`ReminderManager::acknowledge`].
+    pub fn acknowledge_current(&mut self) -> Result<bool, ReminderError> {
+        match self.current_id() {
+            Some(id) => self.acknowledge(&id),
+            None => Ok(false),
+        }
+    }
+
+    /// Hides the reminder for `minutes` from now, and records that in the
+    /// state file. Returns `false` if no reminder has this ID.
+    pub fn snooze(&mut self, id: &str, minutes: u32) -> Result<bool, ReminderError> {
+        if self.get(id).is_none() {
+            return Ok(false);
         }
 
-        active_reminders
-            .get(self.current_index % active_reminders.len())
-            .copied()
+        let now = self.clock.now().with_timezone(&Utc);
+        self.state.prune(now);
+        self.state
+            .snooze(id, now + Duration::minutes(i64::from(minutes)));
+        self.state.save(&self.state_path)?;
+        Ok(true)
+    }
+
+    /// Snoozes the reminder on screen. See [`ReminderManager::snooze`].
+    pub fn snooze_current(&mut self, minutes: u32) -> Result<bool, ReminderError> {
+        match self.current_id() {
+            Some(id) => self.snooze(&id, minutes),
+            None => Ok(false),
+        }
+    }
+
+    /// Whether the reminder is hidden by a snooze right now.
+    pub fn is_snoozed(&self, id: &str) -> bool {
+        self.state
+            .is_snoozed(id, self.clock.now().with_timezone(&Utc))
+    }
+
+    /// Whether the reminder is hidden by an acknowledgement right now.
+    pub fn is_acknowledged(&self, id: &str) -> bool {
+        self.state
+            .is_acknowledged(id, self.clock.now().with_timezone(&Utc))
+    }
+
+    /// The error from the most recent load, if it failed.
+    pub fn last_error(&self) -> Option<&ReminderError> {
+        self.last_error.as_ref()
+    }
+
+    pub fn get_current_reminder(&self) -> Option<&Reminder> {
+        let active = self.active_positions();
+        self.current_in(&active).map(|i| &self.reminders[active[i]])
+    }
+
+    fn current_id(&self) -> Option<String> {
+        let active = self.active_positions();
+        self.current_in(&active)
+            .map(|i| self.ids[active[i]].clone())
     }
 
     pub fn rotate_if_needed(&mut self) {
-        let now = Self::current_timestamp();
-        if now - self.last_rotation >= self.rotation_interval {
-            self.current_index = (self.current_index + 1) % self.get_active_reminder_count().max(1);
+        let now = timestamp(self.clock.as_ref());
+        let active = self.active_positions();
+        let Some(current) = self.current_in(&active) else {
+            return;
+        };
+
+        let shown = active[current];
+        let key = self.ids[shown].clone();
+        if self.current_key.as_ref().is_some_and(|k| *k != key) {
+            // The reminder on screen went inactive or was removed; its
+            // replacement gets a full turn
+            self.last_rotation = now;
+        }
+        self.current_key = Some(key);
+        self.current_position = shown;
+
+        if now.saturating_sub(self.last_rotation) >= self.current_duration() {
+            let reminders: Vec<&Reminder> = active.iter().map(|&p| &self.reminders[p]).collect();
+            let next = active[self.rotation.next(&reminders, current) % active.len()];
+            self.current_key = Some(self.ids[next].clone());
+            self.current_position = next;
             self.last_rotation = now;
         }
     }
@@ -244,22 +1130,27 @@ impl ReminderManager {
         self.get_active_reminder_count()
     }
 
+    /// Position of the reminder on screen among the active ones.
     pub fn get_current_index(&self) -> usize {
-        self.current_index
+        self.current_in(&self.active_positions()).unwrap_or(0)
     }
 
     pub fn time_until_next_rotation(&self) -> u64 {
-        let now = Self::current_timestamp();
-        let elapsed = now - self.last_rotation;
-        if elapsed >= self.rotation_interval {
-            0
-        } else {
-            self.rotation_interval - elapsed
-        }
+        let now = timestamp(self.clock.as_ref());
+        let elapsed = now.saturating_sub(self.last_rotation);
+        self.current_duration().saturating_sub(elapsed)
+    }
+
+    // How long the reminder on screen stays there: its own duration_secs, or
+    // the global rotation interval
+    fn current_duration(&self) -> u64 {
+        self.get_current_reminder()
+            .and_then(|r| r.duration_secs)
+            .unwrap_or_else(|| self.rotation_interval())
     }
 
     pub fn current_time(&self) -> String {
-        Local::now().format("%A, %B %d - %I:%M %p").to_string()
+        self.clock.now().format("%A, %B %d - %I:%M %p").to_string()
     }
 
     pub fn last_file_check(&self) -> &str {
@@ -267,13 +1158,54 @@ impl ReminderManager {
     }
 
     fn get_active_reminder_count(&self) -> usize {
-        self.reminders.iter().filter(|r| r.is_active_now()).count()
+        self.active_positions().len()
     }
 
-    fn current_timestamp() -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
+    // Positions in `reminders` of the reminders active right now
+    fn active_positions(&self) -> Vec<usize> {
+        let now = self.clock.now();
+        let now_utc = now.with_timezone(&Utc);
+        (0..self.reminders.len())
+            .filter(|&p| self.reminders[p].is_active_in(&now, &self.context))
+            .filter(|&p| self.state.is_empty() || !self.state.is_hidden(&self.ids[p], now_utc))
+            .collect()
     }
+
+    // Which of `active` is on screen: the reminder last shown if it's still
+    // active, otherwise the next active one after it in file order
+    fn current_in(&self, active: &[usize]) -> Option<usize> {
+        if active.is_empty() {
+            return None;
+        }
+
+        if let Some(key) = &self.current_key {
+            let matches = |p: usize| self.ids[p] == *key;
+            // Identical reminders share a key, so prefer the same position
+            let same = active
+                .iter()
+                .position(|&p| p == self.current_position && matches(p))
+                .or_else(|| active.iter().position(|&p| matches(p)));
+            if same.is_some() {
+                return same;
+            }
+        }
+
+        Some(
+            active
+                .iter()
+                .position(|&p| p >= self.current_position)
+                .unwrap_or(0),
+        )
+    }
+}
+
+// 64-bit FNV-1a: stable across runs and platforms, unlike DefaultHasher
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn timestamp(clock: &dyn Clock) -> u64 {
+    clock.now().timestamp().max(0) as u64
 }
@@ -0,0 +1,211 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::reminders::Reminder;
+use crate::schedule::{ParseScheduleError, Priority};
+
+/// Decides which active reminder is shown after the current one.
+///
+/// `active` is the list of reminders active right now, in file order, and
+/// `current` the position in it of the reminder on screen. The returned
+/// position is taken modulo `active.len()`. `active` is never empty.
+pub trait RotationStrategy: Send {
+    fn next(&mut self, active: &[&Reminder], current: usize) -> usize;
+}
+
+/// Shows each active reminder in turn, in file order.
+#[derive(Debug, Clone, Default)]
+pub struct RoundRobin;
+
+impl RotationStrategy for RoundRobin {
+    fn next(&mut self, active: &[&Reminder], current: usize) -> usize {
+        (current + 1) % active.len()
+    }
+}
+
+/// Shows higher-priority reminders more often, in proportion to
+/// [`PriorityWeighted::weight`], while spreading each reminder's turns out
+/// evenly rather than showing it several times in a row.
+#[derive(Debug, Clone, Default)]
+pub struct PriorityWeighted {
+    // Smooth weighted round-robin: each reminder's accumulated credit
+    credits: Vec<i64>,
+}
+
+impl PriorityWeighted {
+    /// How many turns a reminder of this priority gets per turn of a low
+    /// priority one.
+    pub fn weight(priority: Priority) -> i64 {
+        match priority {
+            Priority::Low => 1,
+            Priority::Medium => 2,
+            Priority::High => 3,
+            Priority::Urgent => 4,
+        }
+    }
+}
+
+impl RotationStrategy for PriorityWeighted {
+    fn next(&mut self, active: &[&Reminder], _current: usize) -> usize {
+        if self.credits.len() != active.len() {
+            self.credits = vec![0; active.len()];
+        }
+
+        let total: i64 = active.iter().map(|r| Self::weight(r.priority)).sum();
+        for (credit, reminder) in self.credits.iter_mut().zip(active) {
+            *credit += Self::weight(reminder.priority);
+        }
+
+        // The reminder with the most credit is shown; ties go to the first one
+        let mut chosen = 0;
+        for (i, credit) in self.credits.iter().enumerate() {
+            if *credit > self.credits[chosen] {
+                chosen = i;
+            }
+        }
+        self.credits[chosen] -= total;
+        chosen
+    }
+}
+
+/// Shows every active reminder once, in random order, before any is shown
+/// again. A new round never starts with the reminder that ended the last one.
+#[derive(Debug, Clone)]
+pub struct Shuffle {
+    rng: fastrand::Rng,
+    remaining: Vec<usize>,
+    round_len: usize,
+}
+
+impl Shuffle {
+    pub fn new() -> Self {
+        Self::with_rng(fastrand::Rng::new())
+    }
+
+    /// A shuffle with a fixed seed, so the order is reproducible.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(fastrand::Rng::with_seed(seed))
+    }
+
+    fn with_rng(rng: fastrand::Rng) -> Self {
+        Shuffle {
+            rng,
+            remaining: Vec::new(),
+            round_len: 0,
+        }
+    }
+}
+
+impl Default for Shuffle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RotationStrategy for Shuffle {
+    fn next(&mut self, active: &[&Reminder], current: usize) -> usize {
+        // A changed active list invalidates the positions still to be shown
+        if self.round_len != active.len() {
+            self.remaining.clear();
+            self.round_len = active.len();
+        }
+
+        if self.remaining.is_empty() {
+            self.remaining = (0..active.len()).collect();
+            self.rng.shuffle(&mut self.remaining);
+            // `remaining` is used from the back
+            if self.remaining.len() > 1 && self.remaining.last() == Some(&current) {
+                self.remaining.swap(0, active.len() - 1);
+            }
+        }
+        self.remaining.pop().unwrap_or(0)
+    }
+}
+
+/// Keeps an urgent reminder on screen until it's acknowledged or no longer
+/// active, and switches to one at the next rotation after it becomes active.
+/// Without urgent reminders it falls back to round-robin.
+#[derive(Debug, Clone, Default)]
+pub struct PinUrgent;
+
+impl RotationStrategy for PinUrgent {
+    fn next(&mut self, active: &[&Reminder], current: usize) -> usize {
+        let current = current % active.len();
+        if active[current].priority == Priority::Urgent {
+            return current;
+        }
+        active
+            .iter()
+            .position(|r| r.priority == Priority::Urgent)
+            .unwrap_or((current + 1) % active.len())
+    }
+}
+
+/// The built-in rotation strategies, as named in configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum RotationKind {
+    #[default]
+    RoundRobin,
+    PriorityWeighted,
+    Shuffle,
+    PinUrgent,
+}
+
+impl RotationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RotationKind::RoundRobin => "round-robin",
+            RotationKind::PriorityWeighted => "priority-weighted",
+            RotationKind::Shuffle => "shuffle",
+            RotationKind::PinUrgent => "pin-urgent",
+        }
+    }
+
+    pub fn create(&self) -> Box<dyn RotationStrategy> {
+        match self {
+            RotationKind::RoundRobin => Box::new(RoundRobin),
+            RotationKind::PriorityWeighted => Box::new(PriorityWeighted::default()),
+            RotationKind::Shuffle => Box::new(Shuffle::new()),
+            RotationKind::PinUrgent => Box::new(PinUrgent),
+        }
+    }
+}
+
+impl FromStr for RotationKind {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('_', "-").as_str() {
+            "round-robin" => Ok(RotationKind::RoundRobin),
+            "priority-weighted" | "weighted" => Ok(RotationKind::PriorityWeighted),
+            "shuffle" => Ok(RotationKind::Shuffle),
+            "pin-urgent" => Ok(RotationKind::PinUrgent),
+            _ => Err(ParseScheduleError::new(format!(
+                "unknown rotation \"{}\" (expected round-robin, priority-weighted, shuffle or pin-urgent)",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for RotationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<String> for RotationKind {
+    type Error = ParseScheduleError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<RotationKind> for String {
+    fn from(kind: RotationKind) -> Self {
+        kind.as_str().to_string()
+    }
+}
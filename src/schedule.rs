@@ -0,0 +1,471 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::holidays::HolidayCalendar;
+
+/// A value in the reminders file that isn't one of the accepted spellings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseScheduleError(String);
+
+impl ParseScheduleError {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        ParseScheduleError(message.into())
+    }
+}
+
+impl fmt::Display for ParseScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ParseScheduleError {}
+
+/// Settings shared by all reminders that affect when they're active.
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleContext {
+    /// Zone for reminders without their own `timezone`. `None` means the
+    /// zone of the instant being checked, usually the display's local time.
+    pub default_timezone: Option<Tz>,
+    /// Days that `skip_holidays` and `only_on_holidays` refer to.
+    pub holidays: Option<Arc<HolidayCalendar>>,
+    /// Periods defined in the reminders file's settings, by name. These
+    /// can also redefine the built-in morning, afternoon and evening.
+    pub periods: Arc<BTreeMap<String, TimeWindow>>,
+}
+
+impl ScheduleContext {
+    /// The window a reminder's `window` stands for: a named period's
+    /// definition, or the window itself. `None` for a period that isn't
+    /// defined.
+    pub fn resolve(&self, window: &TimeWindow) -> Option<TimeWindow> {
+        match window {
+            TimeWindow::Period(period) => Some(
+                self.periods
+                    .get(period.as_str())
+                    .cloned()
+                    .unwrap_or(TimeWindow::Period(*period)),
+            ),
+            TimeWindow::Named(name) => self.periods.get(name).cloned(),
+            TimeWindow::Range { .. } => Some(window.clone()),
+        }
+    }
+}
+
+/// How important a reminder is, which decides its color.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Urgent,
+}
+
+impl Priority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+            Priority::Urgent => "urgent",
+        }
+    }
+}
+
+impl FromStr for Priority {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "urgent" => Ok(Priority::Urgent),
+            "high" => Ok(Priority::High),
+            "medium" | "important" => Ok(Priority::Medium),
+            "low" | "info" => Ok(Priority::Low),
+            _ => Err(ParseScheduleError(format!(
+                "unknown priority \"{}\" (expected urgent, high, medium or low)",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<String> for Priority {
+    type Error = ParseScheduleError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Priority> for String {
+    fn from(priority: Priority) -> Self {
+        priority.as_str().to_string()
+    }
+}
+
+/// A day of the week, written in full and lowercase in the reminders file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    pub const WORKDAYS: [Weekday; 5] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Weekday::Monday => "monday",
+            Weekday::Tuesday => "tuesday",
+            Weekday::Wednesday => "wednesday",
+            Weekday::Thursday => "thursday",
+            Weekday::Friday => "friday",
+            Weekday::Saturday => "saturday",
+            Weekday::Sunday => "sunday",
+        }
+    }
+}
+
+impl From<chrono::Weekday> for Weekday {
+    fn from(day: chrono::Weekday) -> Self {
+        match day {
+            chrono::Weekday::Mon => Weekday::Monday,
+            chrono::Weekday::Tue => Weekday::Tuesday,
+            chrono::Weekday::Wed => Weekday::Wednesday,
+            chrono::Weekday::Thu => Weekday::Thursday,
+            chrono::Weekday::Fri => Weekday::Friday,
+            chrono::Weekday::Sat => Weekday::Saturday,
+            chrono::Weekday::Sun => Weekday::Sunday,
+        }
+    }
+}
+
+impl FromStr for Weekday {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "monday" | "mon" => Ok(Weekday::Monday),
+            "tuesday" | "tue" => Ok(Weekday::Tuesday),
+            "wednesday" | "wed" => Ok(Weekday::Wednesday),
+            "thursday" | "thu" => Ok(Weekday::Thursday),
+            "friday" | "fri" => Ok(Weekday::Friday),
+            "saturday" | "sat" => Ok(Weekday::Saturday),
+            "sunday" | "sun" => Ok(Weekday::Sunday),
+            _ => Err(ParseScheduleError(format!("unknown weekday \"{}\"", s))),
+        }
+    }
+}
+
+impl fmt::Display for Weekday {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<String> for Weekday {
+    type Error = ParseScheduleError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Weekday> for String {
+    fn from(day: Weekday) -> Self {
+        day.as_str().to_string()
+    }
+}
+
+/// A named part of the day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Period {
+    /// 06:00 until noon
+    Morning,
+    /// Noon until 17:00
+    Afternoon,
+    /// 17:00 until 22:00
+    Evening,
+}
+
+impl Period {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Period::Morning => "morning",
+            Period::Afternoon => "afternoon",
+            Period::Evening => "evening",
+        }
+    }
+
+    fn hours(&self) -> std::ops::Range<u32> {
+        match self {
+            Period::Morning => 6..12,
+            Period::Afternoon => 12..17,
+            Period::Evening => 17..22,
+        }
+    }
+}
+
+/// When during the day a reminder is shown: a named period or an explicit
+/// "HH:MM-HH:MM" range. Ranges are inclusive at both ends, and run past
+/// midnight when the end is before the start.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum TimeWindow {
+    Period(Period),
+    /// A period defined in the settings, by its lowercase name, such as
+    /// "lunch". It only matches once resolved with
+    /// [`ScheduleContext::resolve`].
+    Named(String),
+    Range {
+        start: NaiveTime,
+        end: NaiveTime,
+    },
+}
+
+/// Where a time of day falls relative to a [`TimeWindow`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowMatch {
+    Outside,
+    SameDay,
+    /// Inside the after-midnight part of an overnight range like "22:00-06:00"
+    StartedYesterday,
+}
+
+impl TimeWindow {
+    pub fn matches(&self, current_time: NaiveTime) -> WindowMatch {
+        match *self {
+            TimeWindow::Named(_) => WindowMatch::Outside,
+            TimeWindow::Period(period) => {
+                if period.hours().contains(&current_time.hour()) {
+                    WindowMatch::SameDay
+                } else {
+                    WindowMatch::Outside
+                }
+            }
+            TimeWindow::Range { start, end } => {
+                // The end minute counts as a whole, up to where ends_at has
+                // the range end
+                let current_time = current_time
+                    .with_second(0)
+                    .and_then(|t| t.with_nanosecond(0))
+                    .unwrap_or(current_time);
+                if start <= end {
+                    if current_time >= start && current_time <= end {
+                        return WindowMatch::SameDay;
+                    }
+                } else if current_time >= start {
+                    return WindowMatch::SameDay;
+                } else if current_time <= end {
+                    return WindowMatch::StartedYesterday;
+                }
+                WindowMatch::Outside
+            }
+        }
+    }
+
+    /// When the occurrence of this window that started on `started` is over:
+    /// the end of the period, or the minute after a range's (inclusive) end.
+    /// An unresolved named period is taken to last the day.
+    pub fn ends_at(&self, started: NaiveDate) -> NaiveDateTime {
+        match *self {
+            TimeWindow::Named(_) => started
+                .succ_opt()
+                .unwrap_or(started)
+                .and_time(NaiveTime::MIN),
+            TimeWindow::Period(period) => started
+                .and_hms_opt(period.hours().end, 0, 0)
+                .expect("period ends within the day"),
+            TimeWindow::Range { start, end } => {
+                let day = if start <= end {
+                    started
+                } else {
+                    started.succ_opt().unwrap_or(started)
+                };
+                day.and_time(end) + Duration::minutes(1)
+            }
+        }
+    }
+}
+
+impl FromStr for TimeWindow {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "morning" => return Ok(TimeWindow::Period(Period::Morning)),
+            "afternoon" => return Ok(TimeWindow::Period(Period::Afternoon)),
+            "evening" => return Ok(TimeWindow::Period(Period::Evening)),
+            _ => {}
+        }
+
+        let parse_range = || {
+            let (start_str, end_str) = s.split_once('-')?;
+            let start = NaiveTime::parse_from_str(start_str.trim(), "%H:%M").ok()?;
+            let end = NaiveTime::parse_from_str(end_str.trim(), "%H:%M").ok()?;
+            Some(TimeWindow::Range { start, end })
+        };
+        // Whether a name is defined is checked against the settings it's
+        // loaded with
+        parse_range()
+            .or_else(|| is_period_name(s.trim()).then(|| TimeWindow::Named(s.trim().to_lowercase())))
+            .ok_or_else(|| {
+                ParseScheduleError(format!(
+                    "unrecognised time_range \"{}\" (expected morning, afternoon, evening, a period from the settings or HH:MM-HH:MM)",
+                    s
+                ))
+            })
+    }
+}
+
+impl fmt::Display for TimeWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeWindow::Period(period) => f.write_str(period.as_str()),
+            TimeWindow::Named(name) => f.write_str(name),
+            TimeWindow::Range { start, end } => {
+                write!(f, "{}-{}", start.format("%H:%M"), end.format("%H:%M"))
+            }
+        }
+    }
+}
+
+/// Whether `name` can name a period: letters, digits, `-` and `_`, starting
+/// with a letter.
+pub(crate) fn is_period_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+impl TryFrom<String> for TimeWindow {
+    type Error = ParseScheduleError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<TimeWindow> for String {
+    fn from(window: TimeWindow) -> Self {
+        window.to_string()
+    }
+}
+
+/// One or more time windows; a reminder is in range if any of them matches.
+///
+/// Written as a single string (`"morning"`) or an array of strings
+/// (`["10:00-10:15", "afternoon"]`) in the reminders file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeWindows(Vec<TimeWindow>);
+
+impl TimeWindows {
+    pub fn new(windows: Vec<TimeWindow>) -> Self {
+        TimeWindows(windows)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, TimeWindow> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<TimeWindow> for TimeWindows {
+    fn from(window: TimeWindow) -> Self {
+        TimeWindows(vec![window])
+    }
+}
+
+impl<'a> IntoIterator for &'a TimeWindows {
+    type Item = &'a TimeWindow;
+    type IntoIter = std::slice::Iter<'a, TimeWindow>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// Parses a comma-separated list such as "10:00-10:15, afternoon".
+impl FromStr for TimeWindows {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|window| window.parse())
+            .collect::<Result<Vec<_>, _>>()
+            .map(TimeWindows)
+    }
+}
+
+impl fmt::Display for TimeWindows {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, window) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", window)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for TimeWindows {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0.as_slice() {
+            [window] => window.serialize(serializer),
+            windows => windows.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeWindows {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum OneOrMany {
+            One(String),
+            Many(Vec<String>),
+        }
+
+        let raw = match OneOrMany::deserialize(deserializer)? {
+            OneOrMany::One(window) => vec![window],
+            OneOrMany::Many(windows) => windows,
+        };
+        raw.iter()
+            .map(|window| window.parse())
+            .collect::<Result<Vec<_>, _>>()
+            .map(TimeWindows)
+            .map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,185 @@
+use chrono_tz::Tz;
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::num::{NonZeroU32, NonZeroU64};
+use std::str::FromStr;
+
+use crate::rotation::RotationKind;
+use crate::schedule::{ParseScheduleError, TimeWindow, is_period_name};
+
+/// Display options kept in a versioned reminders file. Anything left out
+/// falls back to the command line or the built-in default, and options given
+/// on the command line win over these.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    /// Seconds each reminder stays on screen, unless it sets duration_secs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval_secs: Option<NonZeroU64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<RotationKind>,
+    /// Zone for reminders without their own `timezone`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<Tz>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<Theme>,
+    /// Snooze lengths offered on screen, in minutes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snooze_minutes: Option<Vec<NonZeroU32>>,
+    /// Named "HH:MM-HH:MM" ranges, such as `"lunch": "12:00-13:00"`, that a
+    /// reminder's `time_range` can use by name. Defining morning, afternoon
+    /// or evening replaces the built-in one.
+    #[serde(
+        default,
+        skip_serializing_if = "BTreeMap::is_empty",
+        deserialize_with = "deserialize_periods"
+    )]
+    pub periods: BTreeMap<String, TimeWindow>,
+}
+
+/// Most snooze lengths the display can offer: one per number key, 1 to 9.
+pub const MAX_SNOOZE_LENGTHS: usize = 9;
+
+impl Settings {
+    /// Checks what the field types can't rule out. Returns one message per
+    /// problem found.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if let Some(snooze_minutes) = &self.snooze_minutes
+            && snooze_minutes.len() > MAX_SNOOZE_LENGTHS
+        {
+            problems.push(format!(
+                "snooze_minutes has {} lengths; at most {} can be given",
+                snooze_minutes.len(),
+                MAX_SNOOZE_LENGTHS
+            ));
+        }
+        problems
+    }
+
+    /// Whether nothing is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Settings::default()
+    }
+
+    /// Applies `other` over these settings, as a later file's: whatever it
+    /// sets replaces the value here, and its periods are added, replacing
+    /// any of the same name.
+    pub fn merge(&mut self, other: Settings) {
+        let Settings {
+            interval_secs,
+            rotation,
+            timezone,
+            theme,
+            snooze_minutes,
+            periods,
+        } = other;
+        self.interval_secs = interval_secs.or(self.interval_secs);
+        self.rotation = rotation.or(self.rotation);
+        self.timezone = timezone.or(self.timezone);
+        self.theme = theme.or(self.theme);
+        self.snooze_minutes = snooze_minutes.or(self.snooze_minutes.take());
+        self.periods.extend(periods);
+    }
+}
+
+// Names are matched case-insensitively, as time_range is read, and each
+// period is a single range so it can't refer to another
+fn deserialize_periods<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, TimeWindow>, D::Error> {
+    let periods = BTreeMap::<String, TimeWindow>::deserialize(deserializer)?;
+    let mut defined = BTreeMap::new();
+    for (name, window) in periods {
+        if !is_period_name(&name) {
+            return Err(de::Error::custom(format!(
+                "period name \"{}\" must start with a letter and have only letters, digits, - and _",
+                name
+            )));
+        }
+        if !matches!(window, TimeWindow::Range { .. }) {
+            return Err(de::Error::custom(format!(
+                "period \"{}\" must be an HH:MM-HH:MM range, not \"{}\"",
+                name, window
+            )));
+        }
+        if defined.insert(name.to_lowercase(), window).is_some() {
+            return Err(de::Error::custom(format!(
+                "period \"{}\" is defined more than once",
+                name.to_lowercase()
+            )));
+        }
+    }
+    Ok(defined)
+}
+
+/// Color theme of the display.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Theme {
+    Dark,
+    Light,
+    /// Follow the desktop's light or dark setting
+    #[default]
+    System,
+}
+
+impl Theme {
+    pub const NAMES: [&'static str; 3] = ["dark", "light", "system"];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Theme::Dark => "dark",
+            Theme::Light => "light",
+            Theme::System => "system",
+        }
+    }
+}
+
+impl From<Theme> for egui::ThemePreference {
+    fn from(theme: Theme) -> Self {
+        match theme {
+            Theme::Dark => egui::ThemePreference::Dark,
+            Theme::Light => egui::ThemePreference::Light,
+            Theme::System => egui::ThemePreference::System,
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "dark" => Ok(Theme::Dark),
+            "light" => Ok(Theme::Light),
+            "system" => Ok(Theme::System),
+            _ => Err(ParseScheduleError::new(format!(
+                "unknown theme \"{}\" (expected dark, light or system)",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<String> for Theme {
+    type Error = ParseScheduleError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Theme> for String {
+    fn from(theme: Theme) -> Self {
+        theme.as_str().to_string()
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::document::ReminderDocument;
+use crate::error::{ReminderError, ValidationIssue};
+use crate::format::Format;
+use crate::reminders::{Reminder, check_entries};
+use crate::settings::Settings;
+
+/// A file that reminders are read from, with its content as read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// Reads the files the reminders at `path` come from: the file itself or,
+/// for a directory, each reminders file in it in name order. See
+/// [`is_source_file`].
+///
+/// Each file's `include`s are read just before it, and theirs before them,
+/// so an including file's settings win over those it includes. A path can
+/// name a file or a directory; a glob pattern reads the reminders files it
+/// matches other than the including file, in name order, and may match none.
+/// A file reached more than once
+/// is read the first time only, and one that includes itself, directly or
+/// through others, is an error.
+pub fn read_sources(path: &Path) -> Result<Vec<SourceFile>, ReminderError> {
+    let paths = if path.is_dir() {
+        directory_files(path)?
+    } else {
+        vec![path.to_path_buf()]
+    };
+    let mut reader = SourceReader::default();
+    for path in paths {
+        reader.read(path)?;
+    }
+    Ok(reader.files)
+}
+
+#[derive(Default)]
+struct SourceReader {
+    files: Vec<SourceFile>,
+    // Canonical paths, as one file can be reached by different names
+    seen: HashSet<PathBuf>,
+    // The files whose includes are being read, each canonical and as named
+    including: Vec<(PathBuf, PathBuf)>,
+}
+
+impl SourceReader {
+    fn read(&mut self, path: PathBuf) -> Result<(), ReminderError> {
+        let canonical = fs::canonicalize(&path).map_err(|e| ReminderError::io(&path, e))?;
+        if let Some(start) = self.including.iter().position(|(c, _)| *c == canonical) {
+            let chain: Vec<String> = self.including[start..]
+                .iter()
+                .map(|(_, named)| named.display().to_string())
+                .chain([path.display().to_string()])
+                .collect();
+            let (_, includer) = &self.including[self.including.len() - 1];
+            return Err(ReminderError::Include {
+                path: includer.clone(),
+                message: format!("include cycle: {}", chain.join(" -> ")),
+            });
+        }
+        if !self.seen.insert(canonical.clone()) {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(&path).map_err(|e| ReminderError::io(&path, e))?;
+        // A file that doesn't parse is reported when it's loaded
+        let include = match Format::from_path(&path).parse_document(&path, &content) {
+            Ok(document) if document.enabled => document.include,
+            _ => Vec::new(),
+        };
+        self.including.push((canonical, path.clone()));
+        for pattern in &include {
+            for included in resolve_include(&path, pattern)? {
+                self.read(included)?;
+            }
+        }
+        self.including.pop();
+
+        self.files.push(SourceFile { path, content });
+        Ok(())
+    }
+}
+
+// The files `pattern`, from `including`'s include, names
+fn resolve_include(including: &Path, pattern: &str) -> Result<Vec<PathBuf>, ReminderError> {
+    let include_error = |message: String| ReminderError::Include {
+        path: including.to_path_buf(),
+        message,
+    };
+    let dir = including.parent().unwrap_or(Path::new(""));
+
+    if !pattern.contains(['*', '?', '[']) {
+        let path = dir.join(pattern);
+        return match fs::metadata(&path) {
+            Ok(metadata) if metadata.is_dir() => directory_files(&path),
+            Ok(_) => Ok(vec![path]),
+            Err(e) => Err(include_error(format!(
+                "can't include \"{}\": {}",
+                pattern, e
+            ))),
+        };
+    }
+
+    // The directory is taken literally, whatever characters it has
+    let full_pattern = if Path::new(pattern).is_absolute() {
+        pattern.to_string()
+    } else {
+        let dir = glob::Pattern::escape(&dir.to_string_lossy());
+        Path::new(&dir).join(pattern).to_string_lossy().into_owned()
+    };
+    let matches = glob::glob(&full_pattern).map_err(|e| {
+        include_error(format!(
+            "invalid include pattern \"{}\": {}",
+            pattern, e.msg
+        ))
+    })?;
+    // `*.json` next to the file matches the file itself, which is meant to
+    // be left out rather than be a cycle
+    let itself = fs::canonicalize(including).ok();
+    let mut paths = Vec::new();
+    for path in matches {
+        let path = path.map_err(|e| ReminderError::io(e.path().to_path_buf(), e.into()))?;
+        if is_source_file(&path) && path.is_file() && fs::canonicalize(&path).ok() != itself {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Whether a file in a reminders directory, or matched by an include
+/// pattern, is read: one with a reminders extension (see
+/// [`Format::for_extension`]) whose name doesn't start with a dot, which
+/// leaves out editors' swap and backup files. State files
+/// (`*.state.json`) and the holiday calendar (`holidays.json`) are left out
+/// too.
+pub fn is_source_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().map(|name| name.to_string_lossy()) else {
+        return false;
+    };
+    let other = name.starts_with('.') || name.ends_with(".state.json") || name == "holidays.json";
+    !other && Format::for_extension(path).is_some()
+}
+
+fn directory_files(dir: &Path) -> Result<Vec<PathBuf>, ReminderError> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| ReminderError::io(dir, e))? {
+        let path = entry.map_err(|e| ReminderError::io(dir, e))?.path();
+        if is_source_file(&path) && path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Like [`crate::lint_reminders`] for each of several files read together,
+/// which may use each other's periods and must not reuse each other's IDs.
+/// Returns one result per file, in order.
+pub fn lint_sources(files: &[SourceFile]) -> Vec<Result<Vec<Reminder>, ReminderError>> {
+    check_sources(files, true)
+        .into_iter()
+        .map(|result| result.map(|document| document.reminders))
+        .collect()
+}
+
+/// The reminders and settings of the enabled files, one after the other.
+pub(crate) struct Merged {
+    pub reminders: Vec<Reminder>,
+    pub settings: Settings,
+    pub files: Vec<LoadedFile>,
+}
+
+/// A file as loaded, with what it takes to edit it later.
+#[derive(Debug, Clone)]
+pub(crate) struct LoadedFile {
+    pub path: PathBuf,
+    pub version: u32,
+    pub include: Vec<String>,
+    pub settings: Settings,
+    /// Where its reminders are in the merged list; empty if it's disabled.
+    pub range: Range<usize>,
+}
+
+/// Combines `files` in order: their reminders one after the other, and
+/// their settings with later files' taking precedence. Fails with the first
+/// file's error if any is invalid.
+pub(crate) fn merge_sources(files: &[SourceFile]) -> Result<Merged, ReminderError> {
+    let mut merged = Merged {
+        reminders: Vec::new(),
+        settings: Settings::default(),
+        files: Vec::with_capacity(files.len()),
+    };
+    for (file, result) in files.iter().zip(check_sources(files, false)) {
+        let document = result?;
+        let start = merged.reminders.len();
+        if document.enabled {
+            merged.settings.merge(document.settings.clone());
+            merged.reminders.extend(document.reminders);
+        }
+        merged.files.push(LoadedFile {
+            path: file.path.clone(),
+            version: document.version,
+            include: document.include,
+            settings: document.settings,
+            range: start..merged.reminders.len(),
+        });
+    }
+    Ok(merged)
+}
+
+fn check_sources(
+    files: &[SourceFile],
+    strict: bool,
+) -> Vec<Result<ReminderDocument, ReminderError>> {
+    let documents: Vec<_> = files
+        .iter()
+        .map(|file| Format::from_path(&file.path).parse_document(&file.path, &file.content))
+        .collect();
+    // Settings first, as a period defined in one file can be used in another
+    let mut periods = BTreeMap::new();
+    for document in documents.iter().flatten().filter(|d| d.enabled) {
+        periods.extend(document.settings.periods.clone());
+    }
+
+    let mut ids: HashMap<String, PathBuf> = HashMap::new();
+    files
+        .iter()
+        .zip(documents)
+        .map(|(file, document)| {
+            let document = document?;
+            let mut defined = periods.clone();
+            defined.extend(document.settings.periods.clone());
+            let document = check_entries(&file.path, document, strict, &defined)?;
+            if !document.enabled {
+                return Ok(document);
+            }
+
+            // Duplicates within the file are already reported
+            let mut issues = Vec::new();
+            for (index, reminder) in document.reminders.iter().enumerate() {
+                let id = match &reminder.id {
+                    Some(id) => id.clone(),
+                    None if strict => reminder.id(),
+                    None => continue,
+                };
+                match ids.get(&id) {
+                    Some(other) if *other != file.path => issues.push(ValidationIssue {
+                        index: Some(index),
+                        text: Some(reminder.text.clone()),
+                        message: match reminder.id {
+                            Some(_) => {
+                                format!("id \"{}\" is already used in {}", id, other.display())
+                            }
+                            None => format!("duplicates a reminder in {}", other.display()),
+                        },
+                    }),
+                    _ => {
+                        ids.insert(id, file.path.clone());
+                    }
+                }
+            }
+            if issues.is_empty() {
+                Ok(document)
+            } else {
+                Err(ReminderError::Invalid {
+                    path: file.path.clone(),
+                    issues,
+                })
+            }
+        })
+        .collect()
+}
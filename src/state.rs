@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::ReminderError;
+
+/// Snooze lengths offered on the display, in minutes, unless configured
+/// otherwise.
+pub const DEFAULT_SNOOZE_MINUTES: [u32; 3] = [5, 15, 60];
+
+/// What has been done to individual reminders from the display, keyed by
+/// reminder ID. Kept in a sidecar file next to the reminders file so that it
+/// survives restarts, without touching the reminders file itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReminderState {
+    /// Acknowledged reminders and when their acknowledged occurrence ends.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub acknowledged: BTreeMap<String, DateTime<Utc>>,
+    /// Snoozed reminders and when their snooze runs out.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub snoozed: BTreeMap<String, DateTime<Utc>>,
+}
+
+impl ReminderState {
+    /// The state file for a reminders file: `work_reminders.json` keeps its
+    /// state in `work_reminders.state.json`.
+    pub fn path_for(reminders_file: &Path) -> PathBuf {
+        let stem = reminders_file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        reminders_file.with_file_name(format!("{}.state.json", stem))
+    }
+
+    /// Reads a state file. A missing file is an empty state.
+    pub fn load(path: &Path) -> Result<Self, ReminderError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(ReminderError::io(path, e)),
+        };
+        serde_json::from_str(&content).map_err(|e| ReminderError::Parse {
+            path: path.to_path_buf(),
+            line: e.line(),
+            column: e.column(),
+            message: crate::reminders::strip_position(&e),
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ReminderError> {
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| ReminderError::io(path, e.into()))?;
+        fs::write(path, json).map_err(|e| ReminderError::io(path, e))
+    }
+
+    /// Hides the reminder until `until`.
+    pub fn acknowledge(&mut self, id: impl Into<String>, until: DateTime<Utc>) {
+        self.acknowledged.insert(id.into(), until);
+    }
+
+    /// Whether the reminder is hidden by an acknowledgement at `now`.
+    pub fn is_acknowledged(&self, id: &str, now: DateTime<Utc>) -> bool {
+        self.acknowledged.get(id).is_some_and(|until| now < *until)
+    }
+
+    /// Hides the reminder until `until`, however its schedule continues.
+    pub fn snooze(&mut self, id: impl Into<String>, until: DateTime<Utc>) {
+        self.snoozed.insert(id.into(), until);
+    }
+
+    pub fn is_snoozed(&self, id: &str, now: DateTime<Utc>) -> bool {
+        self.snoozed.get(id).is_some_and(|until| now < *until)
+    }
+
+    /// Whether the reminder is acknowledged or snoozed at `now`.
+    pub fn is_hidden(&self, id: &str, now: DateTime<Utc>) -> bool {
+        self.is_acknowledged(id, now) || self.is_snoozed(id, now)
+    }
+
+    /// Moves any acknowledgement and snooze of the reminder `from` to `to`,
+    /// for a reminder whose ID changed.
+    pub fn rename(&mut self, from: &str, to: &str) {
+        for entries in [&mut self.acknowledged, &mut self.snoozed] {
+            if let Some(until) = entries.remove(from) {
+                entries.insert(to.to_string(), until);
+            }
+        }
+    }
+
+    /// Whether nothing is acknowledged or snoozed.
+    pub fn is_empty(&self) -> bool {
+        self.acknowledged.is_empty() && self.snoozed.is_empty()
+    }
+
+    /// Forgets entries that have run out by `now`.
+    pub fn prune(&mut self, now: DateTime<Utc>) {
+        self.acknowledged.retain(|_, until| now < *until);
+        self.snoozed.retain(|_, until| now < *until);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::reminders::ReminderManager;
+use crate::source::is_source_file;
+use notify::event::{EventKind, ModifyKind};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::Duration;
+
+/// How long the reminders file has to stay quiet before we reload it. Editors
+/// tend to emit several events per save (truncate, write, chmod, rename).
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Watches the reminders file and reloads the manager when it changes.
+///
+/// The parent directory is watched rather than the file itself so that saves
+/// done via an atomic rename (write to a temp file, rename over the original)
+/// are picked up too. A reminders directory is watched itself, for changes to
+/// any of its reminders files, including ones added or removed. The
+/// directories of included files are watched as well, and any a reload
+/// newly includes from are added. Dropping the watcher stops the background
+/// thread.
+pub struct ReminderWatcher {
+    _watcher: Arc<Mutex<RecommendedWatcher>>,
+}
+
+impl ReminderWatcher {
+    /// Starts watching the manager's file. `on_reload` is called from the
+    /// watcher thread after every reload that actually changed the content.
+    pub fn spawn<F>(manager: Arc<Mutex<ReminderManager>>, on_reload: F) -> notify::Result<Self>
+    where
+        F: Fn() + Send + 'static,
+    {
+        let (file_path, sources) = manager
+            .lock()
+            .map(|m| (m.file_path().to_path_buf(), source_dirs(&m)))
+            .map_err(|_| notify::Error::generic("reminder manager lock poisoned"))?;
+        let (watch_dir, watched) = if file_path.is_dir() {
+            (file_path, Watched::Directory)
+        } else {
+            let file_name = file_path
+                .file_name()
+                .map(|name| name.to_os_string())
+                .ok_or_else(|| notify::Error::generic("reminders path has no file name"))?;
+            let dir = match file_path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+                _ => Path::new(".").to_path_buf(),
+            };
+            (dir, Watched::File(file_name))
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            if let Ok(event) = res
+                && is_relevant(&event, &watched)
+            {
+                let _ = tx.send(());
+            }
+        })?;
+        watcher.watch(&watch_dir, RecursiveMode::NonRecursive)?;
+        let watcher = Arc::new(Mutex::new(watcher));
+        let mut watching = HashSet::from([watch_dir]);
+        watch_new(&Arc::downgrade(&watcher), &mut watching, sources);
+
+        let weak = Arc::downgrade(&watcher);
+        thread::spawn(move || {
+            // Exits once the watcher (and with it the sender) is dropped
+            while rx.recv().is_ok() {
+                while rx.recv_timeout(DEBOUNCE).is_ok() {}
+
+                let reloaded = manager
+                    .lock()
+                    .map(|mut m| m.check_for_updates().then(|| source_dirs(&m)))
+                    .unwrap_or(None);
+                if let Some(sources) = reloaded {
+                    watch_new(&weak, &mut watching, sources);
+                    on_reload();
+                }
+            }
+        });
+
+        Ok(Self { _watcher: watcher })
+    }
+}
+
+// Directories of the files the reminders were read from
+fn source_dirs(manager: &ReminderManager) -> Vec<PathBuf> {
+    manager
+        .source_paths()
+        .iter()
+        .filter_map(|path| path.parent())
+        .map(|dir| match dir.as_os_str().is_empty() {
+            true => PathBuf::from("."),
+            false => dir.to_path_buf(),
+        })
+        .collect()
+}
+
+// Starts watching those of `dirs` that aren't yet. One that can't be watched
+// is skipped, as the reminders it holds have been read regardless.
+fn watch_new(
+    watcher: &Weak<Mutex<RecommendedWatcher>>,
+    watching: &mut HashSet<PathBuf>,
+    dirs: Vec<PathBuf>,
+) {
+    let Some(watcher) = watcher.upgrade() else {
+        return;
+    };
+    let Ok(mut watcher) = watcher.lock() else {
+        return;
+    };
+    for dir in dirs {
+        if !watching.contains(&dir) && watcher.watch(&dir, RecursiveMode::NonRecursive).is_ok() {
+            watching.insert(dir);
+        }
+    }
+}
+
+// What in the watched directory the reminders come from
+enum Watched {
+    File(OsString),
+    Directory,
+}
+
+// Other reminders files in a watched directory may be included, so they're
+// let through too; a reload only happens if the content read changed
+fn is_relevant(event: &Event, watched: &Watched) -> bool {
+    let is_reminders_file = |path: &Path| match watched {
+        Watched::File(file_name) => path.file_name() == Some(file_name.as_os_str()),
+        Watched::Directory => false,
+    };
+
+    let kind_matches = match event.kind {
+        EventKind::Create(_) => true,
+        EventKind::Modify(ModifyKind::Metadata(_)) => false,
+        // Renaming a file away, like removing it, leaves it missing, which
+        // the reload reports while keeping the reminders already loaded
+        EventKind::Modify(_) | EventKind::Remove(_) => true,
+        _ => false,
+    };
+
+    kind_matches
+        && event
+            .paths
+            .iter()
+            .any(|path| is_reminders_file(path) || is_source_file(path))
+}
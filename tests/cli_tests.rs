@@ -0,0 +1,414 @@
+use std::fs;
+use std::process::{Command, Output, Stdio};
+use tempfile::TempDir;
+
+fn reminder_display() -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_reminder-display"));
+    for var in [
+        "REMINDERS_FILE",
+        "REMINDERS_ROTATION",
+        "REMINDERS_TIMEZONE",
+        "REMINDERS_HOLIDAYS",
+        "REMINDERS_SNOOZE_MINUTES",
+    ] {
+        command.env_remove(var);
+    }
+    command
+}
+
+const REMINDERS_JSON: &str = r#"[
+  {
+    "id": "backup",
+    "text": "Check backups",
+    "category": "DevOps",
+    "priority": "high",
+    "time_range": "09:00-12:00",
+    "days": ["monday", "wednesday"]
+  },
+  {
+    "text": "Stretch",
+    "category": "Health",
+    "priority": "low",
+    "time_range": null,
+    "days": null
+  }
+]"#;
+
+fn run_on(dir: &TempDir, args: &[&str]) -> Output {
+    let path = dir.path().join("reminders.json");
+    reminder_display()
+        .arg("--file")
+        .arg(&path)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[cfg(test)]
+mod cli_tests {
+    use super::*;
+
+    #[test]
+    fn test_help_lists_options_and_environment() {
+        let output = reminder_display().arg("--help").output().unwrap();
+        assert!(output.status.success());
+        let help = String::from_utf8_lossy(&output.stdout);
+        for option in [
+            "--file",
+            "--interval",
+            "--rotation",
+            "--windowed",
+            "--monitor",
+            "--theme",
+            "--log-level",
+        ] {
+            assert!(help.contains(option), "missing {}", option);
+        }
+        assert!(help.contains("REMINDERS_ROTATION"));
+    }
+
+    #[test]
+    fn test_invalid_rotation_is_rejected() {
+        let output = reminder_display()
+            .args(["--rotation", "sideways"])
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(2));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("unknown rotation \"sideways\""));
+    }
+
+    #[test]
+    fn test_invalid_environment_value_is_rejected() {
+        let output = reminder_display()
+            .env("REMINDERS_TIMEZONE", "Mars/Base")
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(2));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Mars/Base"));
+    }
+
+    #[test]
+    fn test_too_many_snooze_lengths_are_rejected() {
+        let output = reminder_display()
+            .args(["--snooze-minutes", "1,2,3,4,5,6,7,8,9,10"])
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(2));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("at most 9 snooze lengths"));
+    }
+
+    #[test]
+    fn test_add_edit_and_remove_from_the_terminal() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("reminders.json"), REMINDERS_JSON).unwrap();
+
+        let output = run_on(
+            &temp_dir,
+            &[
+                "add",
+                "Drink water",
+                "--id",
+                "water",
+                "-c",
+                "Health",
+                "--days",
+                "mon,fri",
+            ],
+        );
+        assert!(output.status.success());
+        assert!(
+            !run_on(
+                &temp_dir,
+                &["add", "Again", "--id", "water", "-c", "Health"]
+            )
+            .status
+            .success()
+        );
+
+        assert!(
+            run_on(
+                &temp_dir,
+                &["edit", "backup", "--priority", "urgent", "--unset", "days"]
+            )
+            .status
+            .success()
+        );
+        assert!(run_on(&temp_dir, &["remove", "water"]).status.success());
+        assert!(!run_on(&temp_dir, &["remove", "water"]).status.success());
+
+        let listing = stdout(&run_on(&temp_dir, &["list"]));
+        assert!(listing.contains("backup  [urgent] DevOps: Check backups"));
+        assert!(listing.contains("    09:00-12:00\n"));
+        assert!(listing.contains("Stretch"));
+        assert!(!listing.contains("Drink water"));
+    }
+
+    #[test]
+    fn test_editing_a_reminder_without_an_id_does_not_add_one() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("reminders.json");
+        fs::write(&path, REMINDERS_JSON).unwrap();
+        let listing = stdout(&run_on(&temp_dir, &["list"]));
+        let line = listing
+            .lines()
+            .find(|line| line.contains("Stretch"))
+            .unwrap();
+        let id = line.split_whitespace().next().unwrap();
+
+        let output = run_on(&temp_dir, &["edit", id, "--priority", "high"]);
+        assert!(output.status.success());
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved[1]["priority"], "high");
+        assert!(saved[1].get("id").is_none());
+        assert_eq!(saved[0]["id"], "backup");
+
+        // The new ID is the one list shows
+        let new_id = stdout(&output)
+            .trim()
+            .rsplit(' ')
+            .next()
+            .unwrap()
+            .to_string();
+        assert_ne!(new_id, id);
+        assert!(
+            stdout(&run_on(&temp_dir, &["list"]))
+                .contains(&format!("{}  [high] Health: Stretch", new_id))
+        );
+    }
+
+    #[test]
+    fn test_commands_do_not_create_a_missing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        for args in [&["list"][..], &["preview"], &["add", "Tea", "-c", "Health"]] {
+            let output = run_on(&temp_dir, args);
+            assert_eq!(output.status.code(), Some(1), "{:?}", args);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            assert_eq!(
+                stderr.matches("reminders.json: No such file").count(),
+                1,
+                "{:?}: {}",
+                args,
+                stderr
+            );
+            assert!(
+                !temp_dir.path().join("reminders.json").exists(),
+                "{:?}",
+                args
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_edit_leaves_the_file_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("reminders.json");
+        fs::write(&path, REMINDERS_JSON).unwrap();
+
+        let output = run_on(&temp_dir, &["edit", "backup", "--cron", "0 9 * * 1-5"]);
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("cron can't be combined"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), REMINDERS_JSON);
+    }
+
+    #[test]
+    fn test_preview_shows_reminders_active_at_a_time() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("reminders.json"), REMINDERS_JSON).unwrap();
+
+        // 2026-10-14 is a Wednesday
+        let morning = stdout(&run_on(
+            &temp_dir,
+            &["--timezone", "UTC", "preview", "--at", "2026-10-14 10:00"],
+        ));
+        assert!(morning.contains("2 of 2 reminders shown"));
+        let evening = stdout(&run_on(
+            &temp_dir,
+            &["--timezone", "UTC", "preview", "--at", "2026-10-14 18:00"],
+        ));
+        assert!(evening.contains("1 of 2 reminders shown"));
+        assert!(evening.contains("Stretch"));
+    }
+
+    #[test]
+    fn test_validate_reports_problems() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("reminders.json"),
+            REMINDERS_JSON.replace("\"high\"", "\"severe\""),
+        )
+        .unwrap();
+
+        let output = run_on(&temp_dir, &["validate"]);
+        assert_eq!(output.status.code(), Some(1));
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains("reminder #1 (\"Check backups\")")
+        );
+    }
+
+    #[test]
+    fn test_validate_checks_every_file_given() {
+        let temp_dir = TempDir::new().unwrap();
+        let good = temp_dir.path().join("good.json");
+        let bad = temp_dir.path().join("bad.json");
+        fs::write(&good, REMINDERS_JSON).unwrap();
+        fs::write(
+            &bad,
+            REMINDERS_JSON.replace("\"days\": null", "\"days\": null, \"priorty\": \"high\""),
+        )
+        .unwrap();
+
+        // No display is needed, so this works without one
+        let output = reminder_display()
+            .env_remove("DISPLAY")
+            .env_remove("WAYLAND_DISPLAY")
+            .args(["validate"])
+            .arg(&good)
+            .arg(&bad)
+            .arg(temp_dir.path().join("missing.json"))
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stdout(&output).contains("good.json: 2 reminders, no problems found"));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("bad.json: reminder #2 (\"Stretch\"): unknown field \"priorty\""));
+        assert!(stderr.contains("missing.json"));
+        assert!(stderr.contains("2 of 3 files have problems"));
+    }
+
+    #[test]
+    fn test_migrate_rewrites_a_bare_list() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("reminders.json");
+        fs::write(&path, REMINDERS_JSON).unwrap();
+        let before = stdout(&run_on(&temp_dir, &["list"]));
+
+        let output = run_on(&temp_dir, &["migrate"]);
+        assert!(output.status.success());
+        assert!(stdout(&output).contains("to version 2"));
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with(
+            "{\n  \"version\": 2,\n  \"reminders\": [\n    {\n      \"id\": \"backup\""
+        ));
+        assert_eq!(stdout(&run_on(&temp_dir, &["list"])), before);
+
+        let output = run_on(&temp_dir, &["migrate"]);
+        assert!(output.status.success());
+        assert!(stdout(&output).contains("already version 2"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+    }
+
+    #[test]
+    fn test_settings_apply_unless_given_on_the_command_line() {
+        let temp_dir = TempDir::new().unwrap();
+        let document = format!(
+            r#"{{"version": 2, "settings": {{"interval_secs": 45, "rotation": "shuffle", "timezone": "UTC"}}, "reminders": {}}}"#,
+            REMINDERS_JSON
+        );
+        fs::write(temp_dir.path().join("reminders.json"), document).unwrap();
+
+        let preview = stdout(&run_on(&temp_dir, &["preview", "--at", "2026-10-14 18:00"]));
+        assert!(preview.contains("1 of 2 reminders shown, shuffle"));
+        assert!(preview.contains("Stretch (45s)"));
+
+        let preview = stdout(&run_on(
+            &temp_dir,
+            &[
+                "--interval",
+                "10",
+                "--rotation",
+                "round-robin",
+                "preview",
+                "--at",
+                "2026-10-14 18:00",
+            ],
+        ));
+        assert!(preview.contains("round-robin"));
+        assert!(preview.contains("Stretch (10s)"));
+    }
+
+    #[test]
+    fn test_directory_files_are_listed_and_validated_together() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("reminders.d");
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("10-team.json"), REMINDERS_JSON).unwrap();
+        fs::write(dir.join("20-desk.json"), r#"[{"id": "plants", "text": "Water the plants", "category": "Home", "priority": "low"}]"#).unwrap();
+
+        let listing = stdout(
+            &reminder_display()
+                .arg("--file")
+                .arg(&dir)
+                .arg("list")
+                .output()
+                .unwrap(),
+        );
+        assert!(listing.contains("    09:00-12:00; monday, wednesday  (10-team.json)\n"));
+        assert!(listing.contains("    any time  (20-desk.json)\n"));
+
+        let output = reminder_display()
+            .args(["validate"])
+            .arg(&dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        assert!(stdout(&output).contains("20-desk.json: 1 reminders, no problems found"));
+
+        fs::write(
+            dir.join("30-dup.json"),
+            r#"[{"id": "backup", "text": "Again", "category": "Home", "priority": "low"}]"#,
+        )
+        .unwrap();
+        let output = reminder_display()
+            .args(["validate"])
+            .arg(&dir)
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(1));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr
+                .contains("30-dup.json: reminder #1 (\"Again\"): id \"backup\" is already used in")
+        );
+        assert!(stderr.contains("1 of 3 files has problems"));
+
+        let output = reminder_display()
+            .args(["validate"])
+            .arg(dir.join("missing.json"))
+            .output()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&output.stderr).contains("1 of 1 file has problems"));
+    }
+
+    #[test]
+    fn test_list_stops_quietly_when_the_pipe_closes() {
+        let temp_dir = TempDir::new().unwrap();
+        let entries: Vec<String> = (0..2000)
+            .map(|i| format!(r#"{{"id": "r{}", "text": "Reminder {}", "category": "Test", "priority": "low"}}"#, i, i))
+            .collect();
+        fs::write(
+            temp_dir.path().join("reminders.json"),
+            format!("[{}]", entries.join(",\n")),
+        )
+        .unwrap();
+
+        // More output than a pipe holds, with nobody reading it
+        let mut child = reminder_display()
+            .arg("--file")
+            .arg(temp_dir.path().join("reminders.json"))
+            .arg("list")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        drop(child.stdout.take());
+        let output = child.wait_with_output().unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+    }
+}
@@ -0,0 +1,46 @@
+// Helpers shared by the test files; each uses only some of them
+#![allow(dead_code)]
+
+use chrono::{Local, TimeZone};
+use reminder_display::clock::ManualClock;
+use reminder_display::reminders::{Reminder, ReminderManager};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+/// A clock stopped at Friday 2026-10-16 12:30 local time.
+pub fn clock() -> Arc<ManualClock> {
+    Arc::new(ManualClock::new(
+        Local.with_ymd_and_hms(2026, 10, 16, 12, 30, 0).unwrap(),
+    ))
+}
+
+/// Writes each file under a new temp directory, creating directories as
+/// needed.
+pub fn write_tree(files: &[(&str, &str)]) -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    for (name, content) in files {
+        let path = temp_dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    temp_dir
+}
+
+/// A manager on `path`, a file or directory, at [`clock`]'s time.
+pub fn manager_on(path: &Path) -> ReminderManager {
+    ReminderManager::with_file(path.to_str().unwrap(), clock())
+}
+
+/// Writes `content` to `name` in `dir` and returns a manager on it.
+pub fn manager_with(dir: &TempDir, name: &str, content: &str) -> (ReminderManager, PathBuf) {
+    let path = dir.path().join(name);
+    fs::write(&path, content).unwrap();
+    (manager_on(&path), path)
+}
+
+/// The IDs of the loaded reminders, in order.
+pub fn ids(manager: &ReminderManager) -> Vec<String> {
+    manager.list().iter().map(Reminder::id).collect()
+}
@@ -0,0 +1,195 @@
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use reminder_display::cron::{CronExpression, CronSchedule, MAX_CRON_DURATION_MINS};
+use reminder_display::reminders::Reminder;
+use std::time::Instant;
+
+fn at(month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2026, month, day)
+        .unwrap()
+        .and_hms_opt(hour, minute, 0)
+        .unwrap()
+}
+
+fn local_at(month: u32, day: u32, hour: u32, minute: u32) -> chrono::DateTime<Local> {
+    Local
+        .with_ymd_and_hms(2026, month, day, hour, minute, 0)
+        .unwrap()
+}
+
+fn expr(source: &str) -> CronExpression {
+    source.parse().unwrap()
+}
+
+#[cfg(test)]
+mod cron_tests {
+    use super::*;
+
+    #[test]
+    fn test_weekday_mornings() {
+        let standup = expr("45 8 * * 1-5");
+
+        // Monday 2 November 2026
+        assert!(standup.matches(&at(11, 2, 8, 45)));
+        assert!(!standup.matches(&at(11, 2, 8, 46)));
+        // Saturday 7 November 2026
+        assert!(!standup.matches(&at(11, 7, 8, 45)));
+    }
+
+    #[test]
+    fn test_steps_lists_and_names() {
+        let quarter_hours = expr("*/15 9-17 * * mon,wed,FRI");
+        assert!(quarter_hours.matches(&at(11, 2, 9, 30)));
+        assert!(!quarter_hours.matches(&at(11, 2, 9, 20)));
+        assert!(!quarter_hours.matches(&at(11, 3, 9, 30)));
+
+        let sundays = expr("0 12 * * 7");
+        assert!(sundays.matches(&at(11, 1, 12, 0)));
+
+        let november = expr("0 9 1 nov *");
+        assert!(november.matches(&at(11, 1, 9, 0)));
+        assert!(!november.matches(&at(12, 1, 9, 0)));
+    }
+
+    #[test]
+    fn test_day_of_month_or_day_of_week() {
+        // The 13th, or any Friday
+        let spooky = expr("0 9 13 * 5");
+        assert!(spooky.matches(&at(11, 13, 9, 0)));
+        assert!(spooky.matches(&at(11, 6, 9, 0)));
+        assert!(!spooky.matches(&at(11, 5, 9, 0)));
+    }
+
+    #[test]
+    fn test_shorthands() {
+        assert!(expr("@daily").matches(&at(11, 5, 0, 0)));
+        assert!(expr("@hourly").matches(&at(11, 5, 13, 0)));
+        assert!(!expr("@hourly").matches(&at(11, 5, 13, 1)));
+    }
+
+    #[test]
+    fn test_invalid_expressions_are_rejected() {
+        assert!("0 9 * *".parse::<CronExpression>().is_err());
+        assert!("60 9 * * *".parse::<CronExpression>().is_err());
+        assert!("0 9 * * funday".parse::<CronExpression>().is_err());
+        assert!("0 17-9 * * *".parse::<CronExpression>().is_err());
+        assert!("*/0 * * * *".parse::<CronExpression>().is_err());
+    }
+
+    #[test]
+    fn test_reminder_stays_visible_for_duration() {
+        let reminder: Reminder = serde_json::from_str(
+            r#"{"text": "Standup", "category": "Meetings", "priority": "high",
+                "time_range": null, "days": null,
+                "cron": {"schedule": "45 8 * * 1-5", "duration_mins": 30}}"#,
+        )
+        .unwrap();
+
+        assert!(!reminder.is_active_at(&local_at(11, 2, 8, 44)));
+        assert!(reminder.is_active_at(&local_at(11, 2, 8, 45)));
+        assert!(reminder.is_active_at(&local_at(11, 2, 9, 14)));
+        assert!(!reminder.is_active_at(&local_at(11, 2, 9, 15)));
+        assert!(!reminder.is_active_at(&local_at(11, 7, 9, 0)));
+    }
+
+    #[test]
+    fn test_recent_fires_match_every_minute_of_the_window() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 0, 40, 0).unwrap();
+        for source in [
+            "*/7 * * * *",
+            "59 23 * * *",
+            "0 0 1 * *",
+            "30 0,12 * * 6",
+            "0 0 29 2 *",
+        ] {
+            let schedule = CronSchedule {
+                expression: expr(source),
+                duration_mins: MAX_CRON_DURATION_MINS,
+            };
+            let fires: Vec<_> = schedule.recent_fires(&now).collect();
+            let expected: Vec<_> = (0..i64::from(MAX_CRON_DURATION_MINS))
+                .map(|back| now - Duration::minutes(back))
+                .filter(|time| schedule.expression.matches(&time.naive_utc()))
+                .collect();
+            assert_eq!(fires, expected, "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_latest_match_skips_what_cannot_match() {
+        let leap_day = expr("0 0 29 2 *");
+        let date = |year, month, day| {
+            NaiveDate::from_ymd_opt(year, month, day)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        };
+        assert_eq!(
+            leap_day.latest_match(at(10, 16, 12, 0), date(2001, 1, 1)),
+            Some(date(2024, 2, 29))
+        );
+        assert_eq!(
+            leap_day.latest_match(at(10, 16, 12, 0), at(1, 1, 0, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_day_long_durations_are_cheap_to_check() {
+        // Each check used to look at every minute of the day before
+        let reminder: Reminder = serde_json::from_str(
+            r#"{"text": "Leap day", "category": "Fun", "priority": "low",
+                "cron": {"schedule": "59 23 29 2 *", "duration_mins": 1440}}"#,
+        )
+        .unwrap();
+        let now = local_at(10, 16, 12, 0);
+
+        let started = Instant::now();
+        for _ in 0..50_000 {
+            assert!(!reminder.is_active_at(&now));
+        }
+        assert!(
+            started.elapsed() < std::time::Duration::from_secs(1),
+            "took {:?}",
+            started.elapsed()
+        );
+    }
+
+    #[test]
+    fn test_plain_expression_uses_default_duration() {
+        let reminder: Reminder = serde_json::from_str(
+            r#"{"text": "Hydrate", "category": "Health", "priority": "low",
+                "time_range": null, "days": null, "cron": "0 * * * *"}"#,
+        )
+        .unwrap();
+
+        assert!(reminder.is_active_at(&local_at(11, 2, 10, 14)));
+        assert!(!reminder.is_active_at(&local_at(11, 2, 10, 15)));
+
+        let json = serde_json::to_value(&reminder).unwrap();
+        assert_eq!(json["cron"], "0 * * * *");
+    }
+
+    #[test]
+    fn test_cron_with_time_range_is_flagged() {
+        let reminder: Reminder = serde_json::from_str(
+            r#"{"text": "Both", "category": "Test", "priority": "low",
+                "time_range": "morning", "days": null, "cron": "0 9 * * *"}"#,
+        )
+        .unwrap();
+        assert_eq!(reminder.validate().len(), 1);
+
+        let zero = serde_json::from_str::<CronSchedule>(
+            r#"{"schedule": "0 9 * * *", "duration_mins": 0}"#,
+        );
+        assert!(zero.is_err());
+
+        let week = serde_json::from_str::<CronSchedule>(
+            r#"{"schedule": "0 9 * * 1", "duration_mins": 10080}"#,
+        );
+        assert!(week.unwrap_err().to_string().contains("at most 1440"));
+        let day = serde_json::from_str::<CronSchedule>(
+            r#"{"schedule": "0 9 * * 1", "duration_mins": 1440}"#,
+        );
+        assert!(day.is_ok());
+    }
+}
@@ -0,0 +1,176 @@
+use reminder_display::error::ReminderError;
+use reminder_display::reminders::Reminder;
+use reminder_display::rotation::RotationKind;
+use reminder_display::source::{lint_sources, read_sources};
+use std::fs;
+
+mod common;
+use common::{ids, manager_on, write_tree};
+
+const TEAM_JSON: &str = r#"{
+  "version": 2,
+  "settings": {"interval_secs": 45, "rotation": "shuffle", "periods": {"lunch": "12:00-13:00"}},
+  "reminders": [
+    {"id": "standup", "text": "Standup", "category": "Work", "priority": "high", "time_range": "09:00-10:00"},
+    {"id": "lunch", "text": "Take a lunch break", "category": "Health", "priority": "low", "time_range": "lunch"}
+  ]
+}"#;
+
+const DESK_TOML: &str = r#"version = 2
+
+[settings]
+rotation = "priority-weighted"
+
+# Uses the team's period
+[[reminders]]
+id = "plants"
+text = "Water the plants"
+category = "Home"
+priority = "low"
+time_range = "lunch"
+"#;
+
+#[cfg(test)]
+mod directory_tests {
+    use super::*;
+
+    #[test]
+    fn test_files_are_merged_in_name_order() {
+        let temp_dir = write_tree(&[
+            ("20-desk.toml", DESK_TOML),
+            ("10-team.json", TEAM_JSON),
+            ("notes.txt", "not reminders"),
+            (".10-team.json.swp", "["),
+        ]);
+        let manager = manager_on(temp_dir.path());
+        assert!(manager.last_error().is_none(), "{:?}", manager.last_error());
+
+        assert_eq!(ids(&manager), ["standup", "lunch", "plants"]);
+        assert_eq!(
+            manager.get("standup").unwrap().source.as_deref(),
+            Some(temp_dir.path().join("10-team.json").as_path())
+        );
+        assert_eq!(
+            manager.get("plants").unwrap().source.as_deref(),
+            Some(temp_dir.path().join("20-desk.toml").as_path())
+        );
+
+        // The later file's rotation wins; the earlier file's interval stays
+        assert_eq!(
+            manager.settings().rotation,
+            Some(RotationKind::PriorityWeighted)
+        );
+        assert_eq!(manager.rotation_interval(), 45);
+        assert_eq!(manager.active_reminders().len(), 2);
+    }
+
+    #[test]
+    fn test_disabled_files_are_left_out() {
+        let disabled = DESK_TOML.replace("version = 2\n", "version = 2\nenabled = false\n");
+        let temp_dir = write_tree(&[("10-team.json", TEAM_JSON), ("20-desk.toml", &disabled)]);
+        let manager = manager_on(temp_dir.path());
+
+        assert_eq!(ids(&manager), ["standup", "lunch"]);
+        assert_eq!(manager.settings().rotation, Some(RotationKind::Shuffle));
+    }
+
+    #[test]
+    fn test_ids_must_be_unique_across_files() {
+        let temp_dir = write_tree(&[
+            ("10-team.json", TEAM_JSON),
+            (
+                "20-desk.toml",
+                &DESK_TOML.replace("\"plants\"", "\"standup\""),
+            ),
+        ]);
+        let mut manager = manager_on(temp_dir.path());
+
+        match manager.load_reminders() {
+            Err(ReminderError::Invalid { path, issues }) => {
+                assert_eq!(path, temp_dir.path().join("20-desk.toml"));
+                assert!(
+                    issues[0]
+                        .message
+                        .contains("id \"standup\" is already used in")
+                );
+                assert!(issues[0].message.contains("10-team.json"));
+            }
+            other => panic!("expected a duplicate id, got {:?}", other),
+        }
+
+        let files = read_sources(temp_dir.path()).unwrap();
+        let results = lint_sources(&files);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+    }
+
+    #[test]
+    fn test_edits_go_to_the_reminders_file() {
+        let temp_dir = write_tree(&[("10-team.json", TEAM_JSON), ("20-desk.toml", DESK_TOML)]);
+        let mut manager = manager_on(temp_dir.path());
+
+        let mut plants = manager.get("plants").unwrap().clone();
+        plants.priority = "high".parse().unwrap();
+        manager.upsert(plants).unwrap();
+        let desk = fs::read_to_string(temp_dir.path().join("20-desk.toml")).unwrap();
+        assert!(desk.contains("# Uses the team's period"));
+        assert!(desk.contains("priority = \"high\""));
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("10-team.json")).unwrap(),
+            TEAM_JSON
+        );
+
+        manager.remove("standup").unwrap();
+        assert_eq!(ids(&manager), ["lunch", "plants"]);
+        assert!(
+            !fs::read_to_string(temp_dir.path().join("10-team.json"))
+                .unwrap()
+                .contains("standup")
+        );
+
+        // There's no telling which file a new one belongs in
+        let result = manager.upsert(Reminder {
+            id: Some("tea".to_string()),
+            text: "Tea".to_string(),
+            category: "Health".to_string(),
+            ..Default::default()
+        });
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("add the reminder to one of its files")
+        );
+        assert!(!manager.check_for_updates());
+    }
+
+    #[test]
+    fn test_adding_and_removing_files_is_picked_up() {
+        let temp_dir = write_tree(&[("10-team.json", TEAM_JSON)]);
+        let mut manager = manager_on(temp_dir.path());
+        assert_eq!(manager.list().len(), 2);
+
+        fs::write(temp_dir.path().join("20-desk.toml"), DESK_TOML).unwrap();
+        assert!(manager.check_for_updates());
+        assert_eq!(ids(&manager), ["standup", "lunch", "plants"]);
+
+        fs::rename(
+            temp_dir.path().join("10-team.json"),
+            temp_dir.path().join("10-team.json.disabled"),
+        )
+        .unwrap();
+        assert!(manager.check_for_updates());
+        // Without the team's file there's no lunch period
+        assert!(
+            manager.last_error().unwrap().issues()[0]
+                .message
+                .contains("no period named \"lunch\"")
+        );
+        assert_eq!(ids(&manager), ["standup", "lunch", "plants"]);
+
+        fs::remove_file(temp_dir.path().join("20-desk.toml")).unwrap();
+        assert!(manager.check_for_updates());
+        assert!(manager.last_error().is_none());
+        assert!(manager.list().is_empty());
+    }
+}
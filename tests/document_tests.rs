@@ -0,0 +1,288 @@
+use chrono::{Local, TimeZone};
+use reminder_display::clock::ManualClock;
+use reminder_display::document::{CURRENT_VERSION, ReminderDocument};
+use reminder_display::error::ReminderError;
+use reminder_display::reminders::{
+    Reminder, ReminderManager, migrate_document, parse_document, parse_reminders,
+};
+use reminder_display::rotation::RotationKind;
+use reminder_display::settings::Theme;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+const LEGACY_JSON: &str = r#"[
+  {"text": "Check dashboards", "category": "DevOps", "priority": "high", "time_range": "09:00-17:00", "days": null},
+  {"text": "Stretch", "category": "Health", "priority": "low", "time_range": null, "days": null}
+]"#;
+
+const DOCUMENT_JSON: &str = r#"{
+  "version": 2,
+  "settings": {
+    "interval_secs": 45,
+    "rotation": "priority-weighted",
+    "timezone": "Europe/Berlin",
+    "theme": "dark",
+    "snooze_minutes": [10, 30]
+  },
+  "reminders": [
+    {"text": "Check dashboards", "category": "DevOps", "priority": "high", "time_range": "09:00-17:00", "days": null},
+    {"text": "Stretch", "category": "Health", "priority": "low", "time_range": null, "days": null}
+  ]
+}"#;
+
+const DOCUMENT_TOML: &str = r#"version = 2
+
+[settings]
+interval_secs = 45
+rotation = "priority-weighted"
+
+# Dashboards go stale over the weekend
+[[reminders]]
+text = "Check dashboards"
+category = "DevOps"
+priority = "high"
+time_range = "09:00-17:00"
+"#;
+
+const DOCUMENT_YAML: &str = r#"version: 2
+settings:
+  theme: light
+reminders:
+  - text: Stretch
+    category: Health
+    priority: low
+"#;
+
+fn as_json(reminders: &[Reminder]) -> serde_json::Value {
+    serde_json::to_value(reminders).unwrap()
+}
+
+fn clock() -> Arc<ManualClock> {
+    Arc::new(ManualClock::new(
+        Local.with_ymd_and_hms(2026, 10, 14, 10, 0, 0).unwrap(),
+    ))
+}
+
+#[cfg(test)]
+mod document_tests {
+    use super::*;
+
+    #[test]
+    fn test_bare_list_is_version_1() {
+        let document = parse_document(Path::new("r.json"), LEGACY_JSON).unwrap();
+        assert_eq!(document.version, 1);
+        assert!(document.is_legacy());
+        assert!(document.settings.is_empty());
+        assert_eq!(document.reminders.len(), 2);
+    }
+
+    #[test]
+    fn test_document_settings_are_read() {
+        let document = parse_document(Path::new("r.json"), DOCUMENT_JSON).unwrap();
+        assert_eq!(document.version, CURRENT_VERSION);
+        assert_eq!(document.settings.interval_secs.unwrap().get(), 45);
+        assert_eq!(
+            document.settings.rotation,
+            Some(RotationKind::PriorityWeighted)
+        );
+        assert_eq!(document.settings.timezone, Some(chrono_tz::Europe::Berlin));
+        assert_eq!(document.settings.theme, Some(Theme::Dark));
+        let snooze: Vec<u32> = document
+            .settings
+            .snooze_minutes
+            .unwrap()
+            .iter()
+            .map(|m| m.get())
+            .collect();
+        assert_eq!(snooze, [10, 30]);
+        assert_eq!(
+            as_json(&document.reminders),
+            as_json(&parse_reminders(Path::new("r.json"), LEGACY_JSON).unwrap())
+        );
+
+        let toml = parse_document(Path::new("r.toml"), DOCUMENT_TOML).unwrap();
+        assert_eq!(toml.settings.interval_secs.unwrap().get(), 45);
+        assert_eq!(toml.reminders.len(), 1);
+        let yaml = parse_document(Path::new("r.yaml"), DOCUMENT_YAML).unwrap();
+        assert_eq!(yaml.settings.theme, Some(Theme::Light));
+        assert_eq!(yaml.reminders[0].text, "Stretch");
+    }
+
+    #[test]
+    fn test_bad_documents_are_rejected() {
+        let cases = [
+            (
+                r#"{"version": 3, "reminders": []}"#,
+                "unsupported version 3 (expected 2)",
+            ),
+            (r#"{"reminders": []}"#, "missing field `version`"),
+            (
+                r#"{"version": 2, "settings": {"theme": "blue"}}"#,
+                "unknown theme \"blue\"",
+            ),
+            (
+                r#"{"version": 2, "settings": {"interval_secs": 0}}"#,
+                "nonzero",
+            ),
+            (
+                r#"{"version": 2, "settings": {"intervall": 5}}"#,
+                "unknown field `intervall`",
+            ),
+            (
+                r#"{"version": 2, "remindres": []}"#,
+                "unknown field `remindres`",
+            ),
+            ("3", "expected an array of reminders"),
+        ];
+        for (content, expected) in cases {
+            match parse_document(Path::new("r.json"), content) {
+                Err(ReminderError::Parse { message, .. }) => {
+                    assert!(message.contains(expected), "{}: {}", content, message)
+                }
+                other => panic!("{}: expected a parse error, got {:?}", content, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_too_many_snooze_lengths_are_invalid() {
+        let content = r#"{"version": 2, "settings": {"snooze_minutes": [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]}, "reminders": [
+          {"text": "", "category": "Health", "priority": "low"}
+        ]}"#;
+        let error = parse_reminders(Path::new("r.json"), content).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "r.json: invalid settings and 1 invalid reminder"
+        );
+        assert_eq!(error.issues()[0].index, None);
+        assert_eq!(
+            error.issues()[0].to_string(),
+            "settings: snooze_minutes has 10 lengths; at most 9 can be given"
+        );
+
+        let content = content
+            .replace(", 10]", "]")
+            .replace("\"text\": \"\"", "\"text\": \"Stretch\"");
+        assert_eq!(
+            parse_reminders(Path::new("r.json"), &content)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_migration_keeps_text_and_reminders() {
+        let migrated = migrate_document(Path::new("r.json"), LEGACY_JSON)
+            .unwrap()
+            .unwrap();
+        assert!(migrated.starts_with(
+            "{\n  \"version\": 2,\n  \"reminders\": [\n    {\"text\": \"Check dashboards\""
+        ));
+        let document = parse_document(Path::new("r.json"), &migrated).unwrap();
+        assert!(!document.is_legacy());
+        assert_eq!(
+            as_json(&document.reminders),
+            as_json(&parse_reminders(Path::new("r.json"), LEGACY_JSON).unwrap())
+        );
+
+        let yaml = "# Everyone\n- text: Stretch  # hourly\n  category: Health\n  priority: low\n";
+        let migrated = migrate_document(Path::new("r.yaml"), yaml)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            migrated,
+            "version: 2\nreminders:\n  # Everyone\n  - text: Stretch  # hourly\n    category: Health\n    priority: low\n"
+        );
+
+        let toml = "# Everyone\n[[reminders]]\ntext = \"Stretch\"\ncategory = \"Health\"\npriority = \"low\"\n";
+        let migrated = migrate_document(Path::new("r.toml"), toml)
+            .unwrap()
+            .unwrap();
+        assert_eq!(migrated, format!("version = 2\n\n{}", toml));
+
+        assert_eq!(
+            migrate_document(Path::new("r.json"), DOCUMENT_JSON).unwrap(),
+            None
+        );
+        assert!(migrate_document(Path::new("r.json"), "[{\"text\": 1}]").is_err());
+    }
+
+    #[test]
+    fn test_manager_uses_settings_unless_overridden() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("r.json");
+        fs::write(&path, DOCUMENT_JSON).unwrap();
+
+        let mut manager = ReminderManager::with_file(path.to_str().unwrap(), clock());
+        assert_eq!(manager.file_version(), 2);
+        assert_eq!(manager.rotation_interval(), 45);
+        assert_eq!(manager.settings().theme, Some(Theme::Dark));
+        manager.set_rotation_interval(10);
+        assert_eq!(manager.rotation_interval(), 10);
+
+        // Reloading picks up changed settings
+        fs::write(
+            &path,
+            DOCUMENT_JSON.replace("\"theme\": \"dark\"", "\"theme\": \"light\""),
+        )
+        .unwrap();
+        assert!(manager.check_for_updates());
+        assert_eq!(manager.settings().theme, Some(Theme::Light));
+        assert_eq!(manager.rotation_interval(), 10);
+    }
+
+    #[test]
+    fn test_edits_keep_the_document() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("r.json");
+        fs::write(&path, DOCUMENT_JSON).unwrap();
+
+        let mut manager = ReminderManager::with_file(path.to_str().unwrap(), clock());
+        manager
+            .upsert(Reminder {
+                id: Some("tea".to_string()),
+                text: "Tea".to_string(),
+                category: "Health".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with(DOCUMENT_JSON.split("\n  ]\n}").next().unwrap()));
+        assert!(content.contains(",\n    {\"id\": \"tea\", \"text\": \"Tea\""));
+        let document = parse_document(&path, &content).unwrap();
+        assert_eq!(document.settings.interval_secs.unwrap().get(), 45);
+        assert_eq!(document.reminders.len(), 3);
+
+        manager.remove("tea").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), DOCUMENT_JSON);
+    }
+
+    #[test]
+    fn test_new_files_are_written_at_the_current_version() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["new.json", "new.toml", "new.yaml"] {
+            let path = temp_dir.path().join(name);
+            let mut manager = ReminderManager::with_file(path.to_str().unwrap(), clock());
+            manager.create_defaults().unwrap();
+
+            let document = parse_document(&path, &fs::read_to_string(&path).unwrap()).unwrap();
+            assert_eq!(document.version, CURRENT_VERSION, "{}", name);
+            assert_eq!(
+                as_json(&document.reminders),
+                as_json(manager.list()),
+                "{}",
+                name
+            );
+        }
+
+        let document: ReminderDocument = ReminderDocument::new(Default::default(), Vec::new());
+        assert_eq!(
+            serde_json::to_string(&document).unwrap(),
+            r#"{"version":2,"reminders":[]}"#
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use chrono::Local;
+use reminder_display::clock::ManualClock;
+use reminder_display::error::ReminderError;
+use reminder_display::format::Format;
+use reminder_display::reminders::{Reminder, ReminderManager, parse_reminders};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+mod common;
+use common::manager_with;
+
+const JSON: &str = r#"[
+  {"id": "dash", "text": "Check dashboards", "category": "DevOps", "priority": "high", "time_range": "09:00-17:00", "days": ["monday", "tuesday"], "start_date": "2026-01-01"},
+  {"text": "Stretch", "category": "Health", "priority": "low", "time_range": null, "days": null, "duration_secs": 20}
+]"#;
+
+const TOML: &str = r#"# Team reminders
+
+# Dashboards go stale over the weekend
+[[reminders]]
+id = "dash"
+text = "Check dashboards"
+category = "DevOps"
+priority = "high"  # pages whoever is on call
+# Office hours only
+time_range = "09:00-17:00"
+days = ["monday", "tuesday"]
+start_date = 2026-01-01
+
+[[reminders]]
+text = "Stretch"  # everyone, every hour
+category = "Health"
+priority = "low"
+duration_secs = 20
+"#;
+
+const YAML: &str = r#"# Dashboards go stale over the weekend
+- id: dash
+  text: Check dashboards
+  category: DevOps
+  priority: high
+  time_range: "09:00-17:00"
+  days: [monday, tuesday]
+  start_date: 2026-01-01
+- text: Stretch
+  category: Health
+  priority: low
+  duration_secs: 20
+"#;
+
+const JSON5: &str = r#"[
+  // Dashboards go stale over the weekend
+  {id: "dash", text: "Check dashboards", category: "DevOps", priority: "high",
+   time_range: "09:00-17:00", days: ["monday", "tuesday"], start_date: "2026-01-01"},
+  {text: "Stretch", category: "Health", priority: "low", duration_secs: 20,},
+]"#;
+
+fn as_json(reminders: &[Reminder]) -> serde_json::Value {
+    serde_json::to_value(reminders).unwrap()
+}
+
+#[cfg(test)]
+mod format_tests {
+    use super::*;
+
+    #[test]
+    fn test_format_is_chosen_by_extension() {
+        assert_eq!(Format::from_path(Path::new("a.json")), Format::Json);
+        assert_eq!(Format::from_path(Path::new("a.jsonc")), Format::Json5);
+        assert_eq!(Format::from_path(Path::new("a.JSON5")), Format::Json5);
+        assert_eq!(Format::from_path(Path::new("a.toml")), Format::Toml);
+        assert_eq!(Format::from_path(Path::new("a.yml")), Format::Yaml);
+        assert_eq!(Format::from_path(Path::new("a.yaml")), Format::Yaml);
+        assert_eq!(Format::from_path(Path::new("reminders")), Format::Json);
+    }
+
+    #[test]
+    fn test_every_format_gives_the_same_reminders() {
+        let expected = as_json(&parse_reminders(Path::new("r.json"), JSON).unwrap());
+
+        for (name, content) in [
+            ("r.toml", TOML),
+            ("r.yaml", YAML),
+            ("r.json5", JSON5),
+            ("r.jsonc", JSON5),
+        ] {
+            let reminders = parse_reminders(Path::new(name), content)
+                .unwrap_or_else(|e| panic!("{}: {}", name, e));
+            assert_eq!(as_json(&reminders), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_validation_is_shared() {
+        let yaml = YAML.replace("priority: low", "priority: lowest");
+        let error = parse_reminders(Path::new("r.yaml"), &yaml).unwrap_err();
+        assert_eq!(error.issues().len(), 1);
+        assert_eq!(error.issues()[0].index, Some(1));
+        assert!(error.issues()[0].message.contains("lowest"));
+
+        let toml = TOML.replace("\"monday\"", "\"moonday\"");
+        let error = parse_reminders(Path::new("r.toml"), &toml).unwrap_err();
+        assert!(error.issues()[0].message.contains("moonday"));
+    }
+
+    #[test]
+    fn test_syntax_errors_have_positions() {
+        let cases = [
+            ("r.toml", "[[reminders]]\ntext = \"x\"\ncategory =\n", 3),
+            ("r.yaml", "- text: x\n  category: [\n", 3),
+            ("r.json5", "[\n  {text: }\n]", 2),
+        ];
+        for (name, content, expected_line) in cases {
+            match parse_reminders(Path::new(name), content) {
+                Err(ReminderError::Parse { line, message, .. }) => {
+                    assert_eq!(line, expected_line, "{}: {}", name, message);
+                    assert!(!message.contains('\n'), "{}: {}", name, message);
+                }
+                other => panic!("{}: expected a parse error, got {:?}", name, other),
+            }
+        }
+
+        let error = parse_reminders(Path::new("r.toml"), "reminders = 3").unwrap_err();
+        assert!(error.to_string().contains("[[reminders]]"));
+    }
+
+    #[test]
+    fn test_toml_edits_keep_comments() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut manager, path) = manager_with(&temp_dir, "r.toml", TOML);
+        assert_eq!(manager.list().len(), 2);
+
+        let mut dash = manager.get("dash").unwrap().clone();
+        dash.priority = "urgent".parse().unwrap();
+        dash.start_date = None;
+        manager.upsert(dash).unwrap();
+        manager
+            .upsert(Reminder {
+                id: Some("tea".to_string()),
+                text: "Tea".to_string(),
+                category: "Health".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# Team reminders\n\n# Dashboards go stale over the weekend\n[[reminders]]\nid = \"dash\""));
+        assert!(content.contains("priority = \"urgent\"  # pages whoever is on call\n# Office hours only\ntime_range = \"09:00-17:00\"\ndays = [\"monday\", \"tuesday\"]\n\n"));
+        assert!(content.contains("text = \"Stretch\"  # everyone, every hour"));
+        assert!(content.contains("[[reminders]]\nid = \"tea\""));
+        assert!(!manager.check_for_updates());
+        assert_eq!(manager.list().len(), 3);
+
+        manager.remove("tea").unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            content
+                .split("\n[[reminders]]\nid = \"tea\"")
+                .next()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_edits_that_would_drop_comments_are_refused() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut manager, path) = manager_with(&temp_dir, "r.yaml", YAML);
+
+        let result = manager.remove("dash");
+        assert!(matches!(result, Err(ReminderError::Io { .. })));
+        assert!(result.unwrap_err().to_string().contains("edit it by hand"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), YAML);
+    }
+
+    #[test]
+    fn test_files_without_comments_are_rewritten() {
+        let temp_dir = TempDir::new().unwrap();
+        let yaml = YAML
+            .replace("# Dashboards go stale over the weekend\n", "")
+            .replace("text: Stretch", "text: \"Stretch #daily\"");
+        let json5 = JSON5.replace("  // Dashboards go stale over the weekend\n", "");
+
+        for (name, content) in [("r.yaml", yaml.as_str()), ("r.json5", json5.as_str())] {
+            let (mut manager, path) = manager_with(&temp_dir, name, content);
+            manager.remove("dash").unwrap();
+
+            let saved = parse_reminders(&path, &fs::read_to_string(&path).unwrap()).unwrap();
+            assert_eq!(as_json(&saved), as_json(manager.list()), "{}", name);
+            assert_eq!(saved.len(), 1, "{}", name);
+            assert!(!manager.check_for_updates(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_comments_are_found_outside_strings() {
+        assert!(Format::Yaml.has_comments(YAML));
+        assert!(Format::Yaml.has_comments("- text: x  # note\n"));
+        assert!(!Format::Yaml.has_comments("- text: Don't\n  category: \"#1 # not a comment\"\n"));
+        assert!(!Format::Yaml.has_comments("- text: Ticket#42\n"));
+        assert!(Format::Toml.has_comments(TOML));
+        assert!(!Format::Toml.has_comments("text = 'a # b'\n"));
+        assert!(Format::Json5.has_comments(JSON5));
+        assert!(Format::Json5.has_comments("[/* none */]"));
+        assert!(
+            !Format::Json5
+                .has_comments(r#"[{text: "see http://example.com", category: 'a \' // b'}]"#)
+        );
+        assert!(!Format::Json.has_comments(JSON));
+    }
+
+    #[test]
+    fn test_missing_file_gets_defaults_in_its_format() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["new.toml", "new.yaml"] {
+            let path = temp_dir.path().join(name);
+            let mut manager = ReminderManager::with_file(
+                path.to_str().unwrap(),
+                Arc::new(ManualClock::new(Local::now())),
+            );
+            manager.create_defaults().unwrap();
+
+            let reloaded = parse_reminders(&path, &fs::read_to_string(&path).unwrap()).unwrap();
+            assert_eq!(as_json(&reloaded), as_json(manager.list()), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use chrono::{Local, NaiveDate, TimeZone};
+use reminder_display::ReminderError;
+use reminder_display::clock::ManualClock;
+use reminder_display::holidays::{Holiday, HolidayCalendar};
+use reminder_display::reminders::{Reminder, ReminderManager};
+use reminder_display::schedule::ScheduleContext;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+const ICS: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example//Holidays//EN\r
+BEGIN:VEVENT\r
+UID:christmas@example.com\r
+DTSTART;VALUE=DATE:20241225\r
+DTEND;VALUE=DATE:20241227\r
+SUMMARY:Christmas Day \\, Boxing Day\r
+RRULE:FREQ=YEARLY\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:thanksgiving@example.com\r
+DTSTART;VALUE=DATE:20261126\r
+SUMMARY:Thanks\r
+ giving\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+#[cfg(test)]
+mod holiday_tests {
+    use super::*;
+
+    #[test]
+    fn test_ics_events_with_rrule_and_multiple_days() {
+        let calendar = HolidayCalendar::parse_ics(Path::new("holidays.ics"), ICS).unwrap();
+
+        assert_eq!(calendar.len(), 2);
+        assert_eq!(
+            calendar.holiday_on(date(2026, 12, 25)),
+            Some("Christmas Day , Boxing Day")
+        );
+        assert!(calendar.is_holiday(date(2026, 12, 26)));
+        assert!(!calendar.is_holiday(date(2026, 12, 27)));
+        assert!(!calendar.is_holiday(date(2023, 12, 25)));
+        assert_eq!(
+            calendar.holiday_on(date(2026, 11, 26)),
+            Some("Thanksgiving")
+        );
+        assert!(!calendar.is_holiday(date(2027, 11, 26)));
+    }
+
+    #[test]
+    fn test_ics_errors_report_the_line() {
+        let content =
+            "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART:2026-12-25\nEND:VEVENT\nEND:VCALENDAR\n";
+        match HolidayCalendar::parse_ics(Path::new("holidays.ics"), content) {
+            Err(ReminderError::Parse { line, message, .. }) => {
+                assert_eq!(line, 3);
+                assert!(message.contains("2026-12-25"), "{}", message);
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+
+        let missing_start = "BEGIN:VEVENT\nSUMMARY:Nothing\nEND:VEVENT\n";
+        assert!(matches!(
+            HolidayCalendar::parse_ics(Path::new("holidays.ics"), missing_start),
+            Err(ReminderError::Parse { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_json_calendar() {
+        let content = r#"[
+            "2026-01-01",
+            {"date": "2026-12-24", "end_date": "2026-12-26", "name": "Christmas"}
+        ]"#;
+        let calendar = HolidayCalendar::parse_json(Path::new("holidays.json"), content).unwrap();
+
+        assert!(calendar.is_holiday(date(2026, 1, 1)));
+        assert_eq!(calendar.holiday_on(date(2026, 12, 26)), Some("Christmas"));
+        assert!(!calendar.is_holiday(date(2026, 12, 27)));
+    }
+
+    #[test]
+    fn test_load_picks_format_from_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let ics_path = temp_dir.path().join("holidays.ics");
+        let json_path = temp_dir.path().join("holidays.json");
+        fs::write(&ics_path, ICS).unwrap();
+        fs::write(&json_path, r#"["2026-07-03"]"#).unwrap();
+
+        assert_eq!(HolidayCalendar::load(&ics_path).unwrap().len(), 2);
+        assert!(
+            HolidayCalendar::load(&json_path)
+                .unwrap()
+                .is_holiday(date(2026, 7, 3))
+        );
+        assert!(matches!(
+            HolidayCalendar::load(&temp_dir.path().join("missing.json")),
+            Err(ReminderError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_skip_and_only_on_holidays() {
+        let workday: Reminder = serde_json::from_str(
+            r#"{"text": "Check dashboards", "category": "DevOps", "priority": "high",
+                "time_range": null, "days": ["friday"], "skip_holidays": true}"#,
+        )
+        .unwrap();
+        let day_off: Reminder = serde_json::from_str(
+            r#"{"text": "Enjoy the day off", "category": "Health", "priority": "low",
+                "time_range": null, "days": null, "only_on_holidays": true}"#,
+        )
+        .unwrap();
+
+        let context = ScheduleContext {
+            holidays: Some(Arc::new(HolidayCalendar::new(vec![Holiday::new(
+                "Christmas",
+                date(2026, 12, 25),
+            )]))),
+            ..Default::default()
+        };
+        let christmas = Local.with_ymd_and_hms(2026, 12, 25, 10, 0, 0).unwrap();
+        let next_friday = Local.with_ymd_and_hms(2027, 1, 1, 10, 0, 0).unwrap();
+        let ordinary_friday = Local.with_ymd_and_hms(2026, 12, 18, 10, 0, 0).unwrap();
+
+        assert!(!workday.is_active_in(&christmas, &context));
+        assert!(workday.is_active_in(&ordinary_friday, &context));
+        assert!(day_off.is_active_in(&christmas, &context));
+        assert!(!day_off.is_active_in(&ordinary_friday, &context));
+        assert!(!day_off.is_active_in(&next_friday, &context));
+
+        // Without a calendar nothing counts as a holiday
+        assert!(workday.is_active_at(&christmas));
+        assert!(!day_off.is_active_at(&christmas));
+    }
+
+    #[test]
+    fn test_skip_and_only_together_is_invalid() {
+        let reminder = Reminder {
+            text: "Confused".to_string(),
+            skip_holidays: true,
+            only_on_holidays: true,
+            ..Default::default()
+        };
+        assert_eq!(reminder.validate().len(), 1);
+    }
+
+    #[test]
+    fn test_manager_uses_holiday_calendar() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("reminders.json");
+        fs::write(
+            &path,
+            r#"[{"text": "Standup", "category": "Work", "priority": "high",
+                 "time_range": null, "days": null, "skip_holidays": true}]"#,
+        )
+        .unwrap();
+        let clock = ManualClock::new(Local.with_ymd_and_hms(2026, 12, 25, 10, 0, 0).unwrap());
+        let mut manager = ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock));
+        assert_eq!(manager.get_total_reminders(), 1);
+
+        let calendar =
+            HolidayCalendar::parse_json(Path::new("holidays.json"), r#"["2026-12-25"]"#).unwrap();
+        manager.set_holidays(Some(calendar));
+        assert_eq!(manager.get_total_reminders(), 0);
+    }
+}
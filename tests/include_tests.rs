@@ -0,0 +1,241 @@
+use reminder_display::error::ReminderError;
+use reminder_display::reminders::Reminder;
+use reminder_display::rotation::RotationKind;
+use reminder_display::source::read_sources;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod common;
+use common::{ids, manager_on, write_tree};
+
+const COMPANY_JSON: &str = r#"{
+  "version": 2,
+  "settings": {"rotation": "shuffle", "periods": {"lunch": "12:00-13:00"}},
+  "reminders": [
+    {"id": "timesheet", "text": "Fill in your timesheet", "category": "Admin", "priority": "medium", "days": ["friday"]}
+  ]
+}"#;
+
+const DESK_JSON: &str = r#"{
+  "version": 2,
+  "include": ["../shared/company.json", "teams/*.toml"],
+  "settings": {"rotation": "priority-weighted"},
+  "reminders": [
+    {"id": "plants", "text": "Water the plants", "category": "Home", "priority": "low", "time_range": "lunch"}
+  ]
+}"#;
+
+const TEAM_TOML: &str = r#"version = 2
+
+[[reminders]]
+id = "standup"
+text = "Standup"
+category = "Work"
+priority = "high"
+time_range = "09:00-09:15"
+"#;
+
+fn desk_with_include(include: &str) -> String {
+    DESK_JSON.replace(r#"["../shared/company.json", "teams/*.toml"]"#, include)
+}
+
+#[cfg(test)]
+mod include_tests {
+    use super::*;
+
+    #[test]
+    fn test_included_files_come_first() {
+        let temp_dir = write_tree(&[
+            ("shared/company.json", COMPANY_JSON),
+            ("desk/reminders.json", DESK_JSON),
+            ("desk/teams/ops.toml", TEAM_TOML),
+            (
+                "desk/teams/dev.toml",
+                &TEAM_TOML.replace("standup", "review"),
+            ),
+            ("desk/teams/notes.txt", "not reminders"),
+        ]);
+        let manager = manager_on(&temp_dir.path().join("desk/reminders.json"));
+        assert!(manager.last_error().is_none(), "{:?}", manager.last_error());
+
+        assert_eq!(ids(&manager), ["timesheet", "review", "standup", "plants"]);
+        let source = |id: &str| manager.get(id).unwrap().source.clone().unwrap();
+        assert!(source("timesheet").ends_with("shared/company.json"));
+        assert!(source("standup").ends_with("desk/teams/ops.toml"));
+        assert_eq!(
+            source("plants"),
+            temp_dir.path().join("desk/reminders.json")
+        );
+
+        // The including file's settings win, and it can use the periods it includes
+        assert_eq!(
+            manager.settings().rotation,
+            Some(RotationKind::PriorityWeighted)
+        );
+        assert_eq!(manager.active_reminders().len(), 2);
+        assert_eq!(manager.source_paths().len(), 4);
+    }
+
+    #[test]
+    fn test_includes_are_followed_recursively_and_read_once() {
+        let team = format!("include = [\"../../shared/company.json\"]\n{}", TEAM_TOML);
+        let temp_dir = write_tree(&[
+            ("shared/company.json", COMPANY_JSON),
+            ("desk/reminders.json", DESK_JSON),
+            ("desk/teams/ops.toml", &team),
+        ]);
+
+        let files = read_sources(&temp_dir.path().join("desk/reminders.json")).unwrap();
+        let names: Vec<PathBuf> = files
+            .iter()
+            .map(|file| {
+                file.path
+                    .strip_prefix(temp_dir.path())
+                    .unwrap()
+                    .to_path_buf()
+            })
+            .collect();
+        assert_eq!(
+            names,
+            [
+                Path::new("desk/../shared/company.json"),
+                Path::new("desk/teams/ops.toml"),
+                Path::new("desk/reminders.json")
+            ]
+        );
+
+        // A glob that matches nothing is fine; a missing file isn't
+        let temp_dir = write_tree(&[("reminders.json", &desk_with_include(r#"["teams/*.toml"]"#))]);
+        assert_eq!(
+            read_sources(&temp_dir.path().join("reminders.json"))
+                .unwrap()
+                .len(),
+            1
+        );
+        let temp_dir = write_tree(&[("reminders.json", &desk_with_include(r#"["company.json"]"#))]);
+        let error = read_sources(&temp_dir.path().join("reminders.json")).unwrap_err();
+        assert!(matches!(error, ReminderError::Include { .. }));
+        assert_eq!(error.path(), &temp_dir.path().join("reminders.json"));
+        assert!(error.to_string().contains("can't include \"company.json\""));
+    }
+
+    #[test]
+    fn test_a_sibling_glob_leaves_out_the_file_itself() {
+        let temp_dir = write_tree(&[
+            ("company.json", COMPANY_JSON),
+            ("desk.json", &desk_with_include(r#"["*.json"]"#)),
+            ("desk.state.json", r#"{"acknowledged": {}}"#),
+            ("holidays.json", r#"["2026-12-25"]"#),
+        ]);
+        let manager = manager_on(&temp_dir.path().join("desk.json"));
+        assert!(manager.last_error().is_none(), "{:?}", manager.last_error());
+
+        assert_eq!(ids(&manager), ["timesheet", "plants"]);
+        assert_eq!(manager.source_paths().len(), 2);
+    }
+
+    #[test]
+    fn test_include_cycles_are_reported() {
+        let temp_dir = write_tree(&[
+            ("a.json", &desk_with_include(r#"["b.json"]"#)),
+            ("b.json", r#"{"version": 2, "include": ["c.json"]}"#),
+            ("c.json", r#"{"version": 2, "include": ["b.json"]}"#),
+        ]);
+        let mut manager = manager_on(&temp_dir.path().join("a.json"));
+
+        match manager.load_reminders() {
+            Err(ReminderError::Include { path, message }) => {
+                assert_eq!(path, temp_dir.path().join("c.json"));
+                let (b, c) = (
+                    temp_dir.path().join("b.json"),
+                    temp_dir.path().join("c.json"),
+                );
+                assert_eq!(
+                    message,
+                    format!(
+                        "include cycle: {} -> {} -> {}",
+                        b.display(),
+                        c.display(),
+                        b.display()
+                    )
+                );
+            }
+            other => panic!("expected an include cycle, got {:?}", other),
+        }
+
+        let self_include = r#"{"version": 2, "include": ["./a.json"]}"#;
+        fs::write(temp_dir.path().join("a.json"), self_include).unwrap();
+        assert!(matches!(
+            manager.load_reminders(),
+            Err(ReminderError::Include { .. })
+        ));
+    }
+
+    #[test]
+    fn test_errors_name_the_included_file() {
+        let temp_dir = write_tree(&[
+            (
+                "shared/company.json",
+                &COMPANY_JSON.replace("\"friday\"", "\"fryday\""),
+            ),
+            (
+                "desk/reminders.json",
+                &desk_with_include(r#"["../shared/company.json"]"#),
+            ),
+        ]);
+        let manager = manager_on(&temp_dir.path().join("desk/reminders.json"));
+
+        let error = manager.last_error().unwrap();
+        assert!(error.path().ends_with("shared/company.json"));
+        assert!(error.issues()[0].message.contains("fryday"));
+        assert!(manager.list().is_empty());
+    }
+
+    #[test]
+    fn test_edits_go_to_the_file_the_reminder_is_in() {
+        let temp_dir = write_tree(&[
+            ("shared/company.json", COMPANY_JSON),
+            (
+                "desk/reminders.json",
+                &desk_with_include(r#"["../shared/company.json"]"#),
+            ),
+        ]);
+        let desk = temp_dir.path().join("desk/reminders.json");
+        let mut manager = manager_on(&desk);
+
+        let mut timesheet = manager.get("timesheet").unwrap().clone();
+        timesheet.priority = "high".parse().unwrap();
+        manager.upsert(timesheet).unwrap();
+        assert!(
+            fs::read_to_string(temp_dir.path().join("shared/company.json"))
+                .unwrap()
+                .contains("\"priority\": \"high\"")
+        );
+
+        manager
+            .upsert(Reminder {
+                id: Some("tea".to_string()),
+                text: "Tea".to_string(),
+                category: "Health".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        let content = fs::read_to_string(&desk).unwrap();
+        assert!(content.contains("\"include\": [\"../shared/company.json\"]"));
+        assert!(content.contains("\"tea\""));
+        assert_eq!(ids(&manager), ["timesheet", "plants", "tea"]);
+        assert!(!manager.check_for_updates());
+
+        // Changing an included file is picked up like the file itself
+        fs::write(
+            temp_dir.path().join("shared/company.json"),
+            COMPANY_JSON.replace("Fill in", "Submit"),
+        )
+        .unwrap();
+        assert!(manager.check_for_updates());
+        assert_eq!(
+            manager.get("timesheet").unwrap().text,
+            "Submit your timesheet"
+        );
+    }
+}
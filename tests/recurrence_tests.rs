@@ -0,0 +1,167 @@
+use chrono::{Duration, Local, NaiveDate, TimeZone};
+use reminder_display::recurrence::Recurrence;
+use reminder_display::reminders::Reminder;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn rule(source: &str) -> Recurrence {
+    source.parse().unwrap()
+}
+
+#[cfg(test)]
+mod recurrence_tests {
+    use super::*;
+
+    #[test]
+    fn test_every_second_tuesday() {
+        let fortnightly = rule("DTSTART:20260106\nRRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU");
+
+        assert!(fortnightly.occurs_on(date(2026, 1, 6)));
+        assert!(!fortnightly.occurs_on(date(2026, 1, 13)));
+        assert!(fortnightly.occurs_on(date(2026, 1, 20)));
+        assert!(!fortnightly.occurs_on(date(2026, 1, 21)));
+        // Nothing before DTSTART
+        assert!(!fortnightly.occurs_on(date(2025, 12, 23)));
+    }
+
+    #[test]
+    fn test_last_friday_of_the_month() {
+        let patch_day = rule("DTSTART:20260101 RRULE:FREQ=MONTHLY;BYDAY=-1FR");
+
+        assert!(patch_day.occurs_on(date(2026, 11, 27)));
+        assert!(!patch_day.occurs_on(date(2026, 11, 20)));
+        assert!(patch_day.occurs_on(date(2026, 1, 30)));
+    }
+
+    #[test]
+    fn test_first_business_day_of_the_quarter() {
+        let quarterly =
+            rule("DTSTART:20260101\nRRULE:FREQ=MONTHLY;INTERVAL=3;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=1");
+
+        assert!(quarterly.occurs_on(date(2026, 1, 1)));
+        assert!(quarterly.occurs_on(date(2026, 4, 1)));
+        assert!(quarterly.occurs_on(date(2026, 7, 1)));
+        assert!(quarterly.occurs_on(date(2026, 10, 1)));
+        assert!(!quarterly.occurs_on(date(2026, 2, 2)));
+        assert!(!quarterly.occurs_on(date(2026, 4, 2)));
+        // 1 January 2028 is a Saturday
+        assert!(!quarterly.occurs_on(date(2028, 1, 1)));
+        assert!(quarterly.occurs_on(date(2028, 1, 3)));
+    }
+
+    #[test]
+    fn test_negative_month_day_is_last_day_of_month() {
+        let month_end = rule("DTSTART:20260101\nRRULE:FREQ=MONTHLY;BYMONTHDAY=-1");
+
+        assert!(month_end.occurs_on(date(2026, 2, 28)));
+        assert!(month_end.occurs_on(date(2026, 4, 30)));
+        assert!(!month_end.occurs_on(date(2026, 4, 29)));
+    }
+
+    #[test]
+    fn test_monthly_without_by_rules_uses_dtstart_day() {
+        let monthly = rule("DTSTART:20260115\nRRULE:FREQ=MONTHLY");
+
+        assert!(monthly.occurs_on(date(2026, 3, 15)));
+        assert!(!monthly.occurs_on(date(2026, 3, 16)));
+    }
+
+    #[test]
+    fn test_yearly_ordinal_weekday_in_month() {
+        let thanksgiving = rule("DTSTART:20250101\nRRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=4TH");
+
+        assert!(thanksgiving.occurs_on(date(2026, 11, 26)));
+        assert!(!thanksgiving.occurs_on(date(2026, 11, 19)));
+    }
+
+    #[test]
+    fn test_count_limits_occurrences() {
+        let three_mondays = rule("DTSTART:20260105\nRRULE:FREQ=WEEKLY;BYDAY=MO;COUNT=3");
+
+        assert!(three_mondays.occurs_on(date(2026, 1, 5)));
+        assert!(three_mondays.occurs_on(date(2026, 1, 19)));
+        assert!(!three_mondays.occurs_on(date(2026, 1, 26)));
+    }
+
+    #[test]
+    fn test_count_must_be_reached_within_the_cap() {
+        let daily = rule("DTSTART:20260101\nRRULE:FREQ=DAILY;COUNT=10000");
+        let last = date(2026, 1, 1) + Duration::days(9_999);
+        assert!(daily.occurs_on(last));
+        assert!(!daily.occurs_on(last + Duration::days(1)));
+
+        for source in [
+            "DTSTART:20260101\nRRULE:FREQ=DAILY;COUNT=10001",
+            // February never has a 30th
+            "DTSTART:20260101\nRRULE:FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30;COUNT=1",
+        ] {
+            let error = source.parse::<Recurrence>().unwrap_err();
+            assert!(
+                error.to_string().contains("use UNTIL instead"),
+                "{}: {}",
+                source,
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn test_until_is_inclusive() {
+        let daily = rule("DTSTART:20261101\nRRULE:FREQ=DAILY;UNTIL=20261115T235959Z");
+
+        assert!(daily.occurs_on(date(2026, 11, 15)));
+        assert!(!daily.occurs_on(date(2026, 11, 16)));
+    }
+
+    #[test]
+    fn test_invalid_rules_are_rejected() {
+        assert!("RRULE:FREQ=WEEKLY".parse::<Recurrence>().is_err());
+        assert!("DTSTART:20260101".parse::<Recurrence>().is_err());
+        assert!(
+            "DTSTART:20260101 RRULE:FREQ=HOURLY"
+                .parse::<Recurrence>()
+                .is_err()
+        );
+        assert!(
+            "DTSTART:20260101 RRULE:FREQ=WEEKLY;BYDAY=XX"
+                .parse::<Recurrence>()
+                .is_err()
+        );
+        assert!(
+            "DTSTART:20260101 RRULE:FREQ=WEEKLY;BYDAY=-1FR"
+                .parse::<Recurrence>()
+                .is_err()
+        );
+        assert!(
+            "DTSTART:20260101 RRULE:FREQ=DAILY;BYHOUR=9"
+                .parse::<Recurrence>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_reminder_consults_recurrence() {
+        let reminder: Reminder = serde_json::from_str(
+            r#"{"text": "Patch day", "category": "DevOps", "priority": "high",
+                "time_range": "09:00-12:00", "days": null,
+                "recurrence": "DTSTART:20260101\nRRULE:FREQ=MONTHLY;BYDAY=-1FR"}"#,
+        )
+        .unwrap();
+
+        let patch_day = Local.with_ymd_and_hms(2026, 11, 27, 10, 0, 0).unwrap();
+        let week_before = Local.with_ymd_and_hms(2026, 11, 20, 10, 0, 0).unwrap();
+        let afternoon = Local.with_ymd_and_hms(2026, 11, 27, 15, 0, 0).unwrap();
+        assert!(reminder.is_active_at(&patch_day));
+        assert!(!reminder.is_active_at(&week_before));
+        assert!(!reminder.is_active_at(&afternoon));
+
+        // The rule is written back exactly as it was read
+        let json = serde_json::to_value(&reminder).unwrap();
+        assert_eq!(
+            json["recurrence"],
+            "DTSTART:20260101\nRRULE:FREQ=MONTHLY;BYDAY=-1FR"
+        );
+    }
+}
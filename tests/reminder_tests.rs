@@ -1,10 +1,13 @@
-use chrono::{Local, Timelike};
+use chrono::{Local, TimeZone};
 use reminder_display::reminders::{Reminder, ReminderManager};
+use reminder_display::schedule::{Priority, TimeWindow, Weekday};
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Mutex;
 use tempfile::TempDir;
 
+mod common;
+
 // Global mutex to serialize tests that use environment variables
 static ENV_MUTEX: Mutex<()> = Mutex::new(());
 
@@ -12,13 +15,18 @@ static ENV_MUTEX: Mutex<()> = Mutex::new(());
 mod reminder_unit_tests {
     use super::*;
 
-    fn create_test_reminder(priority: &str, time_range: Option<String>, days: Option<Vec<String>>) -> Reminder {
+    fn create_test_reminder(
+        priority: &str,
+        time_range: Option<String>,
+        days: Option<Vec<String>>,
+    ) -> Reminder {
         Reminder {
             text: "Test reminder".to_string(),
             category: "Test".to_string(),
-            priority: priority.to_string(),
-            time_range,
-            days,
+            priority: priority.parse().unwrap(),
+            time_range: time_range.map(|t| t.parse().unwrap()),
+            days: days.map(|d| d.iter().map(|day| day.parse().unwrap()).collect()),
+            ..Default::default()
         }
     }
 
@@ -27,12 +35,26 @@ mod reminder_unit_tests {
         let high_priority = create_test_reminder("high", None, None);
         let medium_priority = create_test_reminder("medium", None, None);
         let low_priority = create_test_reminder("low", None, None);
-        let unknown_priority = create_test_reminder("unknown", None, None);
 
-        assert_eq!(high_priority.get_color(), egui::Color32::from_rgb(255, 100, 100));
-        assert_eq!(medium_priority.get_color(), egui::Color32::from_rgb(255, 200, 100));
-        assert_eq!(low_priority.get_color(), egui::Color32::from_rgb(100, 200, 255));
-        assert_eq!(unknown_priority.get_color(), egui::Color32::WHITE);
+        assert_eq!(
+            high_priority.get_color(),
+            egui::Color32::from_rgb(255, 100, 100)
+        );
+        assert_eq!(
+            medium_priority.get_color(),
+            egui::Color32::from_rgb(255, 200, 100)
+        );
+        assert_eq!(
+            low_priority.get_color(),
+            egui::Color32::from_rgb(100, 200, 255)
+        );
+    }
+
+    #[test]
+    fn test_reminder_unknown_priority_is_rejected() {
+        assert!("unknown".parse::<Priority>().is_err());
+        assert!("hgih".parse::<Priority>().is_err());
+        assert_eq!("HIGH".parse::<Priority>().unwrap(), Priority::High);
     }
 
     #[test]
@@ -44,7 +66,10 @@ mod reminder_unit_tests {
     #[test]
     fn test_reminder_important_priority_color() {
         let important = create_test_reminder("important", None, None);
-        assert_eq!(important.get_color(), egui::Color32::from_rgb(255, 200, 100));
+        assert_eq!(
+            important.get_color(),
+            egui::Color32::from_rgb(255, 200, 100)
+        );
     }
 
     #[test]
@@ -61,161 +86,147 @@ mod reminder_unit_tests {
 
     #[test]
     fn test_reminder_day_filtering() {
-        let current_day = Local::now().format("%A").to_string().to_lowercase();
-        
-        let active_reminder = create_test_reminder(
-            "medium",
-            None,
-            Some(vec![current_day.clone()])
-        );
-        assert!(active_reminder.is_active_now());
-
-        let tomorrow = match current_day.as_str() {
-            "monday" => "tuesday",
-            "tuesday" => "wednesday",
-            "wednesday" => "thursday",
-            "thursday" => "friday",
-            "friday" => "saturday",
-            "saturday" => "sunday",
-            "sunday" => "monday",
-            _ => "monday",
-        };
+        // 2026-10-14 is a Wednesday
+        let wednesday = Local.with_ymd_and_hms(2026, 10, 14, 10, 0, 0).unwrap();
 
-        let inactive_reminder = create_test_reminder(
-            "medium",
-            None,
-            Some(vec![tomorrow.to_string()])
-        );
-        assert!(!inactive_reminder.is_active_now());
+        let active_reminder =
+            create_test_reminder("medium", None, Some(vec!["wednesday".to_string()]));
+        assert!(active_reminder.is_active_at(&wednesday));
+
+        let inactive_reminder =
+            create_test_reminder("medium", None, Some(vec!["thursday".to_string()]));
+        assert!(!inactive_reminder.is_active_at(&wednesday));
     }
 
     #[test]
     fn test_reminder_time_range_keywords() {
-        let now = Local::now();
-        let hour = now.hour();
+        let morning_reminder = create_test_reminder("medium", Some("morning".to_string()), None);
+        let afternoon_reminder =
+            create_test_reminder("medium", Some("afternoon".to_string()), None);
+        let evening_reminder = create_test_reminder("medium", Some("evening".to_string()), None);
 
-        let morning_reminder = create_test_reminder(
-            "medium",
-            Some("morning".to_string()),
-            None
-        );
-        assert_eq!(morning_reminder.is_active_now(), hour >= 6 && hour < 12);
+        for hour in 0..24 {
+            let now = Local.with_ymd_and_hms(2026, 10, 14, hour, 30, 0).unwrap();
+            assert_eq!(
+                morning_reminder.is_active_at(&now),
+                (6..12).contains(&hour),
+                "{}:30",
+                hour
+            );
+            assert_eq!(
+                afternoon_reminder.is_active_at(&now),
+                (12..17).contains(&hour),
+                "{}:30",
+                hour
+            );
+            assert_eq!(
+                evening_reminder.is_active_at(&now),
+                (17..22).contains(&hour),
+                "{}:30",
+                hour
+            );
+        }
+    }
 
-        let afternoon_reminder = create_test_reminder(
-            "medium",
-            Some("afternoon".to_string()),
-            None
-        );
-        assert_eq!(afternoon_reminder.is_active_now(), hour >= 12 && hour < 17);
+    #[test]
+    fn test_reminder_time_range_format() {
+        let now = Local.with_ymd_and_hms(2026, 10, 14, 14, 30, 0).unwrap();
 
-        let evening_reminder = create_test_reminder(
-            "medium",
-            Some("evening".to_string()),
-            None
-        );
-        assert_eq!(evening_reminder.is_active_now(), hour >= 17 && hour < 22);
+        let active_reminder = create_test_reminder("medium", Some("13:30-15:30".to_string()), None);
+        assert!(active_reminder.is_active_at(&now));
+
+        let past_reminder = create_test_reminder("medium", Some("11:30-12:30".to_string()), None);
+        assert!(!past_reminder.is_active_at(&now));
+
+        // Both ends are included
+        let edges = create_test_reminder("medium", Some("14:30-15:00".to_string()), None);
+        assert!(edges.is_active_at(&now));
+        assert!(edges.is_active_at(&Local.with_ymd_and_hms(2026, 10, 14, 15, 0, 0).unwrap()));
+        assert!(!edges.is_active_at(&Local.with_ymd_and_hms(2026, 10, 14, 15, 1, 0).unwrap()));
     }
 
     #[test]
-    fn test_reminder_time_range_format() {
-        let now = Local::now();
-        let current_time = now.time();
-        
-        let start = current_time
-            .overflowing_sub_signed(chrono::Duration::hours(1))
-            .0;
-        let end = current_time
-            .overflowing_add_signed(chrono::Duration::hours(1))
-            .0;
-        
-        let time_range = format!(
-            "{:02}:{:02}-{:02}:{:02}",
-            start.hour(),
-            start.minute(),
-            end.hour(),
-            end.minute()
-        );
-        
-        let active_reminder = create_test_reminder(
-            "medium",
-            Some(time_range),
-            None
-        );
-        assert!(active_reminder.is_active_now());
-
-        let past_start = current_time
-            .overflowing_sub_signed(chrono::Duration::hours(3))
-            .0;
-        let past_end = current_time
-            .overflowing_sub_signed(chrono::Duration::hours(2))
-            .0;
-        
-        let past_range = format!(
-            "{:02}:{:02}-{:02}:{:02}",
-            past_start.hour(),
-            past_start.minute(),
-            past_end.hour(),
-            past_end.minute()
-        );
-        
-        let past_reminder = create_test_reminder(
-            "medium",
-            Some(past_range),
-            None
+    fn test_reminder_invalid_time_range_is_rejected() {
+        assert!("9am-5pm".parse::<TimeWindow>().is_err());
+        assert!("25:00-26:00".parse::<TimeWindow>().is_err());
+
+        // Any other name may be a period from the settings, so a misspelling
+        // is caught when the file is loaded
+        let content = r#"[{"text": "T", "category": "C", "priority": "low", "time_range": "mornin", "days": null}]"#;
+        let error =
+            reminder_display::reminders::parse_reminders(std::path::Path::new("r.json"), content)
+                .unwrap_err();
+        assert!(
+            error.issues()[0]
+                .message
+                .contains("no period named \"mornin\"")
         );
-        assert!(!past_reminder.is_active_now());
     }
 
     #[test]
-    fn test_reminder_invalid_time_range_defaults_to_active() {
+    fn test_reminder_misspelled_weekday_is_rejected() {
+        let error = "wensday".parse::<Weekday>().unwrap_err();
+        assert!(error.to_string().contains("wensday"));
+        assert_eq!("Wednesday".parse::<Weekday>().unwrap(), Weekday::Wednesday);
+    }
+
+    #[test]
+    fn test_reminder_serializes_back_to_legacy_strings() {
         let reminder = create_test_reminder(
-            "medium",
-            Some("invalid-format".to_string()),
-            None
+            "important",
+            Some("09:00-17:00".to_string()),
+            Some(vec!["Monday".to_string()]),
         );
-        assert!(reminder.is_active_now());
+        let json = serde_json::to_value(&reminder).unwrap();
+
+        assert_eq!(json["priority"], "medium");
+        assert_eq!(json["time_range"], "09:00-17:00");
+        assert_eq!(json["days"], serde_json::json!(["monday"]));
     }
 
     #[test]
     fn test_reminder_combined_day_and_time_filtering() {
-        let current_day = Local::now().format("%A").to_string().to_lowercase();
-        let now = Local::now();
-        let current_time = now.time();
-        
-        let start = current_time
-            .overflowing_sub_signed(chrono::Duration::hours(1))
-            .0;
-        let end = current_time
-            .overflowing_add_signed(chrono::Duration::hours(1))
-            .0;
-        
-        let time_range = format!(
-            "{:02}:{:02}-{:02}:{:02}",
-            start.hour(),
-            start.minute(),
-            end.hour(),
-            end.minute()
-        );
+        // 2026-10-14 is a Wednesday
+        let wednesday_10am = Local.with_ymd_and_hms(2026, 10, 14, 10, 0, 0).unwrap();
 
         let active_reminder = create_test_reminder(
             "medium",
-            Some(time_range.clone()),
-            Some(vec![current_day.clone()])
+            Some("09:00-11:00".to_string()),
+            Some(vec!["wednesday".to_string()]),
         );
-        assert!(active_reminder.is_active_now());
+        assert!(active_reminder.is_active_at(&wednesday_10am));
 
         let wrong_day_reminder = create_test_reminder(
             "medium",
-            Some(time_range.clone()),
-            Some(vec!["nonexistentday".to_string()])
+            Some("09:00-11:00".to_string()),
+            Some(vec!["thursday".to_string()]),
+        );
+        assert!(!wrong_day_reminder.is_active_at(&wednesday_10am));
+
+        // Past midnight, the day is the one the window started on
+        let thursday_0030 = Local.with_ymd_and_hms(2026, 10, 15, 0, 30, 0).unwrap();
+        let overnight_reminder = create_test_reminder(
+            "medium",
+            Some("23:00-01:00".to_string()),
+            Some(vec!["wednesday".to_string()]),
+        );
+        assert!(overnight_reminder.is_active_at(&thursday_0030));
+        let thursday_reminder = create_test_reminder(
+            "medium",
+            Some("23:00-01:00".to_string()),
+            Some(vec!["thursday".to_string()]),
         );
-        assert!(!wrong_day_reminder.is_active_now());
+        assert!(!thursday_reminder.is_active_at(&thursday_0030));
     }
 }
 
 #[cfg(test)]
 mod reminder_manager_tests {
     use super::*;
+    use reminder_display::clock::ManualClock;
+    use reminder_display::clock::SystemClock;
+    use reminder_display::error::ReminderError;
+    use std::sync::Arc;
 
     fn create_test_file(dir: &TempDir, filename: &str, reminders: Vec<Reminder>) -> PathBuf {
         let path = dir.path().join(filename);
@@ -232,27 +243,29 @@ mod reminder_manager_tests {
             Reminder {
                 text: "Test 1".to_string(),
                 category: "Cat1".to_string(),
-                priority: "high".to_string(),
+                priority: Priority::High,
                 time_range: None,
                 days: None,
+                ..Default::default()
             },
             Reminder {
                 text: "Test 2".to_string(),
                 category: "Cat2".to_string(),
-                priority: "low".to_string(),
+                priority: Priority::Low,
                 time_range: None,
                 days: None,
+                ..Default::default()
             },
         ];
-        
+
         let file_path = create_test_file(&temp_dir, "test_reminders.json", reminders.clone());
         unsafe {
             std::env::set_var("REMINDERS_FILE", file_path.to_str().unwrap());
         }
-        
+
         let manager = ReminderManager::new();
         assert_eq!(manager.get_total_reminders(), 2);
-        
+
         unsafe {
             std::env::remove_var("REMINDERS_FILE");
         }
@@ -266,68 +279,82 @@ mod reminder_manager_tests {
         unsafe {
             std::env::set_var("REMINDERS_FILE", file_path.to_str().unwrap());
         }
-        
+
         let manager = ReminderManager::new();
-        
+
         assert!(file_path.exists());
         assert!(manager.get_total_reminders() > 0);
-        
+
         unsafe {
             std::env::remove_var("REMINDERS_FILE");
         }
     }
 
+    #[test]
+    fn test_manager_reports_a_missing_file_instead_of_creating_it() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("typo.json");
+        let manager =
+            ReminderManager::with_file(file_path.to_str().unwrap(), Arc::new(SystemClock));
+        assert!(!file_path.exists());
+        assert!(
+            matches!(manager.last_error(), Some(ReminderError::Io { source, .. }) if source.kind() == std::io::ErrorKind::NotFound)
+        );
+
+        // Nor is the file recreated when it goes away later
+        let file_path = create_test_file(
+            &temp_dir,
+            "reminders.json",
+            vec![Reminder {
+                text: "Kept".to_string(),
+                category: "Test".to_string(),
+                ..Default::default()
+            }],
+        );
+        let mut manager =
+            ReminderManager::with_file(file_path.to_str().unwrap(), Arc::new(SystemClock));
+        fs::remove_file(&file_path).unwrap();
+        assert!(manager.check_for_updates());
+        assert!(!file_path.exists());
+        assert!(manager.last_error().is_some());
+        assert_eq!(manager.list().len(), 1);
+    }
+
     #[test]
     fn test_manager_filters_active_reminders() {
-        let _guard = ENV_MUTEX.lock().unwrap();
         let temp_dir = TempDir::new().unwrap();
-        let current_day = Local::now().format("%A").to_string().to_lowercase();
-        let tomorrow = match current_day.as_str() {
-            "monday" => "tuesday",
-            "tuesday" => "wednesday",
-            "wednesday" => "thursday",
-            "thursday" => "friday",
-            "friday" => "saturday",
-            "saturday" => "sunday",
-            "sunday" => "monday",
-            _ => "monday",
-        };
-        
         let reminders = vec![
             Reminder {
                 text: "Active today".to_string(),
                 category: "Test".to_string(),
-                priority: "high".to_string(),
+                priority: Priority::High,
                 time_range: None,
-                days: Some(vec![current_day.clone()]),
+                days: Some(vec![Weekday::Wednesday]),
+                ..Default::default()
             },
             Reminder {
                 text: "Not active today".to_string(),
                 category: "Test".to_string(),
-                priority: "low".to_string(),
+                priority: Priority::Low,
                 time_range: None,
-                days: Some(vec![tomorrow.to_string()]),
+                days: Some(vec![Weekday::Thursday]),
+                ..Default::default()
             },
             Reminder {
                 text: "Always active".to_string(),
                 category: "Test".to_string(),
-                priority: "medium".to_string(),
+                priority: Priority::Medium,
                 time_range: None,
                 days: None,
+                ..Default::default()
             },
         ];
-        
+
         let file_path = create_test_file(&temp_dir, "test_reminders.json", reminders);
-        unsafe {
-            std::env::set_var("REMINDERS_FILE", file_path.to_str().unwrap());
-        }
-        
-        let manager = ReminderManager::new();
+        // 2026-10-14 is a Wednesday
+        let clock = ManualClock::new(Local.with_ymd_and_hms(2026, 10, 14, 10, 0, 0).unwrap());
+        let manager = ReminderManager::with_file(file_path.to_str().unwrap(), Arc::new(clock));
         assert_eq!(manager.get_total_reminders(), 2); // Only active reminders counted
-        
-        unsafe {
-            std::env::remove_var("REMINDERS_FILE");
-        }
     }
 
     #[test]
@@ -338,42 +365,45 @@ mod reminder_manager_tests {
             Reminder {
                 text: "Reminder 1".to_string(),
                 category: "Test".to_string(),
-                priority: "high".to_string(),
+                priority: Priority::High,
                 time_range: None,
                 days: None,
+                ..Default::default()
             },
             Reminder {
                 text: "Reminder 2".to_string(),
                 category: "Test".to_string(),
-                priority: "medium".to_string(),
+                priority: Priority::Medium,
                 time_range: None,
                 days: None,
+                ..Default::default()
             },
             Reminder {
                 text: "Reminder 3".to_string(),
                 category: "Test".to_string(),
-                priority: "low".to_string(),
+                priority: Priority::Low,
                 time_range: None,
                 days: None,
+                ..Default::default()
             },
         ];
-        
+
         let file_path = create_test_file(&temp_dir, "test_reminders.json", reminders);
         unsafe {
             std::env::set_var("REMINDERS_FILE", file_path.to_str().unwrap());
         }
-        
+
         let mut manager = ReminderManager::new();
         let _initial_index = manager.get_current_index();
-        
+
         // Force rotation by setting last_rotation to past
         std::thread::sleep(std::time::Duration::from_millis(100));
         manager.rotate_if_needed();
-        
+
         // Note: Since rotate_if_needed checks the time interval,
         // we might need to modify the manager's internal state for testing
         // This is a limitation of the current design
-        
+
         unsafe {
             std::env::remove_var("REMINDERS_FILE");
         }
@@ -383,26 +413,25 @@ mod reminder_manager_tests {
     fn test_manager_time_until_next_rotation() {
         let _guard = ENV_MUTEX.lock().unwrap();
         let temp_dir = TempDir::new().unwrap();
-        let reminders = vec![
-            Reminder {
-                text: "Test".to_string(),
-                category: "Test".to_string(),
-                priority: "high".to_string(),
-                time_range: None,
-                days: None,
-            },
-        ];
-        
+        let reminders = vec![Reminder {
+            text: "Test".to_string(),
+            category: "Test".to_string(),
+            priority: Priority::High,
+            time_range: None,
+            days: None,
+            ..Default::default()
+        }];
+
         let file_path = create_test_file(&temp_dir, "test_reminders.json", reminders);
         unsafe {
             std::env::set_var("REMINDERS_FILE", file_path.to_str().unwrap());
         }
-        
+
         let manager = ReminderManager::new();
         let time_until = manager.time_until_next_rotation();
-        
+
         assert!(time_until <= 30); // Default rotation interval is 30 seconds
-        
+
         unsafe {
             std::env::remove_var("REMINDERS_FILE");
         }
@@ -417,15 +446,15 @@ mod reminder_manager_tests {
         unsafe {
             std::env::set_var("REMINDERS_FILE", file_path.to_str().unwrap());
         }
-        
+
         let manager = ReminderManager::new();
         let time_str = manager.current_time();
-        
+
         // Check format includes day, month, time
         assert!(time_str.contains(","));
         assert!(time_str.contains("-"));
         assert!(time_str.contains(":"));
-        
+
         unsafe {
             std::env::remove_var("REMINDERS_FILE");
         }
@@ -435,54 +464,1007 @@ mod reminder_manager_tests {
     fn test_manager_reload_reminders() {
         let _guard = ENV_MUTEX.lock().unwrap();
         let temp_dir = TempDir::new().unwrap();
-        let initial_reminders = vec![
-            Reminder {
-                text: "Initial".to_string(),
-                category: "Test".to_string(),
-                priority: "high".to_string(),
-                time_range: None,
-                days: None,
-            },
-        ];
-        
+        let initial_reminders = vec![Reminder {
+            text: "Initial".to_string(),
+            category: "Test".to_string(),
+            priority: Priority::High,
+            time_range: None,
+            days: None,
+            ..Default::default()
+        }];
+
         let file_path = create_test_file(&temp_dir, "test_reminders.json", initial_reminders);
         unsafe {
             std::env::set_var("REMINDERS_FILE", file_path.to_str().unwrap());
         }
-        
+
         // Verify the file exists and env var is set before creating manager
         assert!(file_path.exists(), "Test file should exist");
-        assert_eq!(std::env::var("REMINDERS_FILE").unwrap(), file_path.to_str().unwrap());
-        
+        assert_eq!(
+            std::env::var("REMINDERS_FILE").unwrap(),
+            file_path.to_str().unwrap()
+        );
+
         let mut manager = ReminderManager::new();
-        assert_eq!(manager.get_total_reminders(), 1, "Manager should load 1 reminder from file");
-        
+        assert_eq!(
+            manager.get_total_reminders(),
+            1,
+            "Manager should load 1 reminder from file"
+        );
+
         // Update file with more reminders
         let updated_reminders = vec![
             Reminder {
                 text: "Updated 1".to_string(),
                 category: "Test".to_string(),
-                priority: "high".to_string(),
+                priority: Priority::High,
                 time_range: None,
                 days: None,
+                ..Default::default()
             },
             Reminder {
                 text: "Updated 2".to_string(),
                 category: "Test".to_string(),
-                priority: "medium".to_string(),
+                priority: Priority::Medium,
                 time_range: None,
                 days: None,
+                ..Default::default()
             },
         ];
-        
+
         let json = serde_json::to_string_pretty(&updated_reminders).unwrap();
         fs::write(&file_path, json).unwrap();
-        
+
         manager.check_for_updates();
         assert_eq!(manager.get_total_reminders(), 2);
-        
+
         unsafe {
             std::env::remove_var("REMINDERS_FILE");
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_manager_skips_reload_when_content_unchanged() {
+        let _guard = ENV_MUTEX.lock().unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let reminders = vec![Reminder {
+            text: "Unchanged".to_string(),
+            category: "Test".to_string(),
+            priority: Priority::High,
+            time_range: None,
+            days: None,
+            ..Default::default()
+        }];
+
+        let file_path = create_test_file(&temp_dir, "test_reminders.json", reminders.clone());
+        unsafe {
+            std::env::set_var("REMINDERS_FILE", file_path.to_str().unwrap());
+        }
+
+        let mut manager = ReminderManager::new();
+        assert!(!manager.check_for_updates(), "Nothing changed on disk");
+
+        // Rewriting identical content is not a change either
+        create_test_file(&temp_dir, "test_reminders.json", reminders);
+        assert!(!manager.check_for_updates());
+
+        fs::write(&file_path, "[]").unwrap();
+        assert!(manager.check_for_updates());
+        assert_eq!(manager.get_total_reminders(), 0);
+
+        unsafe {
+            std::env::remove_var("REMINDERS_FILE");
+        }
+    }
+}
+
+#[cfg(test)]
+mod clock_tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use reminder_display::clock::ManualClock;
+    use std::sync::Arc;
+
+    // Monday, 2 November 2026
+    fn monday_at(hour: u32, minute: u32) -> chrono::DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 11, 2, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn reminder(text: &str, time_range: Option<&str>, days: Option<Vec<&str>>) -> Reminder {
+        Reminder {
+            text: text.to_string(),
+            category: "Test".to_string(),
+            priority: Priority::Medium,
+            time_range: time_range.map(|t| t.parse().unwrap()),
+            days: days.map(|d| d.into_iter().map(|s| s.parse().unwrap()).collect()),
+            ..Default::default()
+        }
+    }
+
+    fn write_reminders(dir: &TempDir, reminders: &[Reminder]) -> PathBuf {
+        let path = dir.path().join("reminders.json");
+        fs::write(&path, serde_json::to_string_pretty(reminders).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_is_active_at_named_periods() {
+        let morning = reminder("m", Some("morning"), None);
+        let afternoon = reminder("a", Some("afternoon"), None);
+        let evening = reminder("e", Some("evening"), None);
+
+        assert!(morning.is_active_at(&monday_at(6, 0)));
+        assert!(!morning.is_active_at(&monday_at(12, 0)));
+        assert!(afternoon.is_active_at(&monday_at(12, 0)));
+        assert!(!afternoon.is_active_at(&monday_at(17, 0)));
+        assert!(evening.is_active_at(&monday_at(21, 59)));
+        assert!(!evening.is_active_at(&monday_at(22, 0)));
+    }
+
+    #[test]
+    fn test_is_active_at_range_and_days() {
+        let weekday_hours = reminder("w", Some("09:00-17:00"), Some(vec!["monday", "tuesday"]));
+
+        assert!(weekday_hours.is_active_at(&monday_at(9, 0)));
+        assert!(weekday_hours.is_active_at(&monday_at(17, 0)));
+        assert!(!weekday_hours.is_active_at(&monday_at(8, 59)));
+        assert!(!weekday_hours.is_active_at(&(monday_at(10, 0) + Duration::days(2))));
+    }
+
+    #[test]
+    fn test_range_does_not_leak_past_midnight() {
+        let late = reminder("late", Some("22:00-23:59"), Some(vec!["monday"]));
+
+        assert!(late.is_active_at(&monday_at(23, 30)));
+        assert!(!late.is_active_at(&(monday_at(0, 1) + Duration::days(1))));
+    }
+
+    #[test]
+    fn test_manager_uses_injected_clock_for_filtering() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_reminders(
+            &temp_dir,
+            &[
+                reminder("Morning", Some("morning"), None),
+                reminder("Afternoon", Some("afternoon"), None),
+                reminder("Always", None, None),
+            ],
+        );
+        let clock = ManualClock::new(monday_at(9, 0));
+        let manager = ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock.clone()));
+
+        assert_eq!(manager.get_total_reminders(), 2);
+        assert_eq!(manager.current_time(), "Monday, November 02 - 09:00 AM");
+
+        clock.set(monday_at(23, 0));
+        assert_eq!(manager.get_total_reminders(), 1);
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Always");
+    }
+
+    #[test]
+    fn test_manager_rotation_with_manual_clock() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_reminders(
+            &temp_dir,
+            &[
+                reminder("First", None, None),
+                reminder("Second", None, None),
+                reminder("Third", None, None),
+            ],
+        );
+        let clock = ManualClock::new(monday_at(9, 0));
+        let mut manager =
+            ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock.clone()));
+
+        assert_eq!(manager.get_current_reminder().unwrap().text, "First");
+        assert_eq!(manager.time_until_next_rotation(), 30);
+
+        clock.advance(Duration::seconds(29));
+        manager.rotate_if_needed();
+        assert_eq!(manager.get_current_reminder().unwrap().text, "First");
+        assert_eq!(manager.time_until_next_rotation(), 1);
+
+        clock.advance(Duration::seconds(1));
+        manager.rotate_if_needed();
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Second");
+
+        clock.advance(Duration::seconds(60));
+        manager.rotate_if_needed();
+        manager.rotate_if_needed();
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Third");
+
+        clock.advance(Duration::seconds(30));
+        manager.rotate_if_needed();
+        assert_eq!(manager.get_current_reminder().unwrap().text, "First");
+    }
+
+    #[test]
+    fn test_manager_honours_per_reminder_duration() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_reminders(
+            &temp_dir,
+            &[
+                Reminder {
+                    duration_secs: Some(90),
+                    ..reminder("Long", None, None)
+                },
+                Reminder {
+                    duration_secs: Some(10),
+                    ..reminder("Short", None, None)
+                },
+                reminder("Default", None, None),
+            ],
+        );
+        let clock = ManualClock::new(monday_at(9, 0));
+        let mut manager =
+            ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock.clone()));
+
+        assert_eq!(manager.time_until_next_rotation(), 90);
+        clock.advance(Duration::seconds(30));
+        manager.rotate_if_needed();
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Long");
+
+        clock.advance(Duration::seconds(60));
+        manager.rotate_if_needed();
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Short");
+        assert_eq!(manager.time_until_next_rotation(), 10);
+
+        clock.advance(Duration::seconds(10));
+        manager.rotate_if_needed();
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Default");
+        assert_eq!(manager.time_until_next_rotation(), 30);
+    }
+
+    #[test]
+    fn test_current_reminder_survives_active_set_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_reminders(
+            &temp_dir,
+            &[
+                reminder("Morning", Some("morning"), None),
+                reminder("First", None, None),
+                reminder("Second", None, None),
+            ],
+        );
+        let clock = ManualClock::new(monday_at(11, 59) + Duration::seconds(20));
+        let mut manager =
+            ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock.clone()));
+
+        clock.advance(Duration::seconds(30));
+        manager.rotate_if_needed();
+        assert_eq!(manager.get_current_reminder().unwrap().text, "First");
+        assert_eq!(manager.get_current_index(), 1);
+
+        // "Morning" drops out at noon; "First" stays on screen
+        clock.advance(Duration::seconds(15));
+        manager.rotate_if_needed();
+        assert_eq!(manager.get_current_reminder().unwrap().text, "First");
+        assert_eq!(manager.get_current_index(), 0);
+        assert_eq!(manager.time_until_next_rotation(), 15);
+    }
+
+    #[test]
+    fn test_inactive_current_reminder_moves_on_in_file_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_reminders(
+            &temp_dir,
+            &[
+                reminder("First", None, None),
+                reminder("Morning", Some("morning"), None),
+                reminder("Last", None, None),
+            ],
+        );
+        let clock = ManualClock::new(monday_at(11, 59));
+        let mut manager =
+            ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock.clone()));
+
+        clock.advance(Duration::seconds(30));
+        manager.rotate_if_needed();
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Morning");
+
+        clock.advance(Duration::seconds(35));
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Last");
+        manager.rotate_if_needed();
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Last");
+        assert_eq!(manager.time_until_next_rotation(), 30);
+    }
+
+    #[test]
+    fn test_current_reminder_survives_reload() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_reminders(
+            &temp_dir,
+            &[
+                reminder("First", None, None),
+                reminder("Second", None, None),
+            ],
+        );
+        let clock = ManualClock::new(monday_at(9, 0));
+        let mut manager =
+            ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock.clone()));
+
+        clock.advance(Duration::seconds(30));
+        manager.rotate_if_needed();
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Second");
+
+        write_reminders(
+            &temp_dir,
+            &[
+                reminder("New", None, None),
+                reminder("Second", None, None),
+                reminder("First", None, None),
+            ],
+        );
+        assert!(manager.check_for_updates());
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Second");
+
+        clock.advance(Duration::seconds(30));
+        manager.rotate_if_needed();
+        assert_eq!(manager.get_current_reminder().unwrap().text, "First");
+    }
+
+    #[test]
+    fn test_zero_duration_is_invalid() {
+        let reminder = Reminder {
+            duration_secs: Some(0),
+            ..reminder("Blink", None, None)
+        };
+        assert_eq!(reminder.validate().len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod overnight_range_tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    // Friday, 6 November 2026
+    fn friday_at(hour: u32, minute: u32) -> chrono::DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 11, 6, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn night_shift(days: Option<Vec<&str>>) -> Reminder {
+        Reminder {
+            text: "On-call night shift".to_string(),
+            category: "On-call".to_string(),
+            priority: Priority::High,
+            time_range: Some("22:00-06:00".parse().unwrap()),
+            days: days.map(|d| d.into_iter().map(|s| s.parse().unwrap()).collect()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_overnight_range_wraps_past_midnight() {
+        let reminder = night_shift(None);
+
+        assert!(reminder.is_active_at(&friday_at(22, 0)));
+        assert!(reminder.is_active_at(&friday_at(23, 59)));
+        assert!(reminder.is_active_at(&friday_at(0, 0)));
+        assert!(reminder.is_active_at(&friday_at(6, 0)));
+        assert!(!reminder.is_active_at(&friday_at(6, 1)));
+        assert!(!reminder.is_active_at(&friday_at(12, 0)));
+        assert!(!reminder.is_active_at(&friday_at(21, 59)));
+    }
+
+    #[test]
+    fn test_overnight_range_days_apply_to_start_day() {
+        let reminder = night_shift(Some(vec!["friday"]));
+
+        // Friday evening, and the early hours of Saturday belong to Friday's shift
+        assert!(reminder.is_active_at(&friday_at(23, 0)));
+        assert!(reminder.is_active_at(&(friday_at(3, 0) + Duration::days(1))));
+
+        // Early Friday morning belongs to Thursday's shift
+        assert!(!reminder.is_active_at(&friday_at(3, 0)));
+        // Saturday evening is a new, unscheduled shift
+        assert!(!reminder.is_active_at(&(friday_at(23, 0) + Duration::days(1))));
+    }
+
+    #[test]
+    fn test_overnight_range_sunday_into_monday() {
+        let reminder = night_shift(Some(vec!["sunday"]));
+        let monday_early = friday_at(2, 0) + Duration::days(3);
+
+        assert!(reminder.is_active_at(&monday_early));
+    }
+}
+
+#[cfg(test)]
+mod error_reporting_tests {
+    use super::*;
+    use crate::common::manager_with;
+    use reminder_display::clock::SystemClock;
+    use reminder_display::error::ReminderError;
+    use reminder_display::reminders::{lint_reminders, parse_reminders};
+    use std::path::Path;
+    use std::sync::Arc;
+
+    const VALID: &str = r#"[
+  {"text": "Valid", "category": "Test", "priority": "high", "time_range": null, "days": null}
+]"#;
+
+    #[test]
+    fn test_malformed_json_reports_position_and_keeps_old_list() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut manager, path) = manager_with(&temp_dir, "reminders.json", VALID);
+        assert!(manager.last_error().is_none());
+
+        fs::write(&path, "[\n  {\"text\": \"Broken\",\n  }\n]").unwrap();
+        let error = manager.load_reminders().unwrap_err();
+
+        match &error {
+            ReminderError::Parse { line, column, .. } => {
+                assert_eq!((*line, *column), (3, 3));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(error.to_string().contains(":3:3: "));
+        assert!(manager.last_error().is_some());
+        assert_eq!(manager.get_total_reminders(), 1);
+
+        fs::write(&path, VALID.replace("Valid", "Fixed")).unwrap();
+        assert!(manager.check_for_updates());
+        assert!(manager.last_error().is_none());
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Fixed");
+    }
+
+    #[test]
+    fn test_validation_lists_every_bad_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let (manager, _) = manager_with(
+            &temp_dir,
+            "reminders.json",
+            r#"[
+  {"text": "Fine", "category": "Test", "priority": "high", "time_range": "09:00-17:00", "days": ["monday"]},
+  {"text": "Bad range", "category": "Test", "priority": "high", "time_range": "9am to 5pm", "days": null},
+  {"text": "Bad day", "category": "Test", "priority": "low", "time_range": null, "days": ["wensday"]},
+  {"text": "No category", "priority": "low", "time_range": null, "days": null}
+]"#,
+        );
+
+        let error = manager.last_error().expect("file should be rejected");
+        let issues = error.issues();
+        assert_eq!(issues.len(), 3);
+        assert_eq!(issues[0].index, Some(1));
+        assert!(issues[0].message.contains("9am to 5pm"));
+        assert_eq!(issues[1].index, Some(2));
+        assert_eq!(issues[1].text.as_deref(), Some("Bad day"));
+        assert!(issues[1].message.contains("wensday"));
+        assert_eq!(issues[2].index, Some(3));
+        assert!(issues[2].message.contains("category"));
+
+        // Nothing from a rejected file is shown
+        assert_eq!(manager.get_total_reminders(), 0);
+    }
+
+    #[test]
+    fn test_every_problem_in_an_entry_is_reported() {
+        let content = r#"[
+  {"text": "Typos", "category": "Test", "priority": "severe", "time_range": "9am-5pm", "days": ["tusday"]}
+]"#;
+
+        let error = parse_reminders(Path::new("typos.json"), content).unwrap_err();
+        let messages: Vec<&str> = error
+            .issues()
+            .iter()
+            .map(|issue| issue.message.as_str())
+            .collect();
+        assert_eq!(messages.len(), 3);
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.starts_with("priority: ") && m.contains("severe"))
+        );
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.starts_with("time_range: ") && m.contains("9am-5pm"))
+        );
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.starts_with("days: ") && m.contains("tusday"))
+        );
+        // Still one reminder, however many problems it has
+        assert_eq!(error.to_string(), "typos.json: 1 invalid reminder");
+    }
+
+    #[test]
+    fn test_lint_rejects_unknown_fields_and_duplicates() {
+        let content = r#"[
+  {"text": "Stretch", "category": "Health", "priority": "low", "time_range": null, "days": null, "tme_range": "09:00-10:00"},
+  {"text": "Stretch", "category": "Health", "priority": "low", "time_range": null, "days": null}
+]"#;
+
+        // The display tolerates both
+        assert_eq!(
+            parse_reminders(Path::new("lint.json"), content)
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let error = lint_reminders(Path::new("lint.json"), content).unwrap_err();
+        let issues = error.issues();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].index, Some(0));
+        assert_eq!(issues[0].message, "unknown field \"tme_range\"");
+        assert_eq!(issues[1].index, Some(1));
+        assert_eq!(issues[1].message, "duplicates reminder #1");
+    }
+
+    #[test]
+    fn test_unwritable_default_file_is_reported() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("missing-dir").join("reminders.json");
+        let mut manager = ReminderManager::with_file(path.to_str().unwrap(), Arc::new(SystemClock));
+
+        assert!(matches!(
+            manager.create_defaults(),
+            Err(ReminderError::Io { .. })
+        ));
+        assert!(matches!(
+            manager.last_error(),
+            Some(ReminderError::Io { .. })
+        ));
+    }
+}
+
+#[cfg(test)]
+mod multiple_window_tests {
+    use super::*;
+    use chrono::TimeZone;
+    use reminder_display::schedule::TimeWindows;
+
+    // Tuesday, 3 November 2026
+    fn tuesday_at(hour: u32, minute: u32) -> chrono::DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 11, 3, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn parse(json: &str) -> Reminder {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_any_window_in_list_matches() {
+        let stretch = parse(
+            r#"{"text": "Stand up and stretch", "category": "Health", "priority": "medium",
+                "time_range": ["10:00-10:15", "14:00-14:15", "16:00-16:15"], "days": null}"#,
+        );
+
+        assert!(stretch.is_active_at(&tuesday_at(10, 5)));
+        assert!(stretch.is_active_at(&tuesday_at(14, 15)));
+        assert!(stretch.is_active_at(&tuesday_at(16, 0)));
+        assert!(!stretch.is_active_at(&tuesday_at(10, 16)));
+        assert!(!stretch.is_active_at(&tuesday_at(15, 0)));
+    }
+
+    #[test]
+    fn test_named_periods_and_ranges_can_be_mixed() {
+        let reminder = parse(
+            r#"{"text": "Mixed", "category": "Test", "priority": "low",
+                "time_range": ["morning", "22:00-02:00"], "days": ["tuesday"]}"#,
+        );
+
+        assert!(reminder.is_active_at(&tuesday_at(7, 0)));
+        assert!(reminder.is_active_at(&tuesday_at(23, 0)));
+        assert!(!reminder.is_active_at(&tuesday_at(14, 0)));
+        // Early Tuesday belongs to Monday's overnight window
+        assert!(!reminder.is_active_at(&tuesday_at(1, 0)));
+    }
+
+    #[test]
+    fn test_single_window_round_trips_as_string() {
+        let single: TimeWindows = serde_json::from_str(r#""09:00-17:00""#).unwrap();
+        assert_eq!(serde_json::to_string(&single).unwrap(), r#""09:00-17:00""#);
+
+        let many: TimeWindows = serde_json::from_str(r#"["morning", "14:00-14:15"]"#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&many).unwrap(),
+            r#"["morning","14:00-14:15"]"#
+        );
+        assert_eq!(many.to_string(), "morning, 14:00-14:15");
+    }
+
+    #[test]
+    fn test_invalid_window_in_list_is_rejected() {
+        let result = serde_json::from_str::<TimeWindows>(r#"["morning", "lunch time"]"#);
+        assert!(result.unwrap_err().to_string().contains("lunch time"));
+
+        let content = r#"[{"text": "T", "category": "C", "priority": "low", "time_range": ["morning", "lunchtime"], "days": null}]"#;
+        let error =
+            reminder_display::reminders::parse_reminders(std::path::Path::new("r.json"), content)
+                .unwrap_err();
+        assert!(error.issues()[0].message.contains("lunchtime"));
+    }
+}
+
+#[cfg(test)]
+mod named_period_tests {
+    use super::*;
+    use chrono::TimeZone;
+    use reminder_display::clock::ManualClock;
+    use reminder_display::reminders::parse_document;
+    use reminder_display::schedule::ScheduleContext;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    const DOCUMENT: &str = r#"{
+  "version": 2,
+  "settings": {"periods": {"lunch": "12:00-13:00", "Night": "22:00-06:00", "morning": "07:30-12:00"}},
+  "reminders": [
+    {"id": "eat", "text": "Eat lunch", "category": "Health", "priority": "low", "time_range": "lunch", "days": null},
+    {"id": "sleep", "text": "Go home", "category": "Health", "priority": "high", "time_range": ["night"], "days": ["tuesday"]},
+    {"id": "plan", "text": "Plan the day", "category": "Work", "priority": "medium", "time_range": "morning", "days": null}
+  ]
+}"#;
+
+    // Tuesday, 3 November 2026
+    fn tuesday_at(hour: u32, minute: u32) -> chrono::DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 11, 3, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn context_and_reminders() -> (ScheduleContext, Vec<Reminder>) {
+        let document = parse_document(Path::new("r.json"), DOCUMENT).unwrap();
+        let context = ScheduleContext {
+            periods: Arc::new(document.settings.periods),
+            ..Default::default()
+        };
+        (context, document.reminders)
+    }
+
+    #[test]
+    fn test_defined_periods_can_be_used_by_name() {
+        let (context, reminders) = context_and_reminders();
+        let (lunch, night) = (&reminders[0], &reminders[1]);
+
+        assert!(lunch.is_active_in(&tuesday_at(12, 30), &context));
+        assert!(!lunch.is_active_in(&tuesday_at(13, 30), &context));
+        // Names are case-insensitive, and periods can run past midnight
+        assert!(night.is_active_in(&tuesday_at(23, 0), &context));
+        assert!(!night.is_active_in(&tuesday_at(5, 0), &context));
+        assert_eq!(lunch.time_range.as_ref().unwrap().to_string(), "lunch");
+
+        // Without the settings they're never active
+        assert!(!lunch.is_active_at(&tuesday_at(12, 30)));
+    }
+
+    #[test]
+    fn test_built_in_periods_can_be_redefined() {
+        let (context, reminders) = context_and_reminders();
+        let plan = &reminders[2];
+
+        assert!(!plan.is_active_in(&tuesday_at(7, 0), &context));
+        assert!(plan.is_active_in(&tuesday_at(7, 45), &context));
+        assert!(plan.is_active_at(&tuesday_at(7, 0)));
+    }
+
+    #[test]
+    fn test_bad_period_definitions_are_rejected() {
+        let cases = [
+            (r#"{"lunch": "noonish"}"#, "noonish"),
+            (r#"{"9to5": "09:00-17:00"}"#, "must start with a letter"),
+            (r#"{"early": "morning"}"#, "must be an HH:MM-HH:MM range"),
+            (
+                r#"{"lunch": "12:00-13:00", "LUNCH": "12:30-13:30"}"#,
+                "defined more than once",
+            ),
+        ];
+        for (periods, expected) in cases {
+            let content = format!(
+                r#"{{"version": 2, "settings": {{"periods": {}}}}}"#,
+                periods
+            );
+            let error = parse_document(Path::new("r.json"), &content).unwrap_err();
+            assert!(
+                error.to_string().contains(expected),
+                "{}: {}",
+                periods,
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn test_manager_follows_periods_in_the_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("r.json");
+        fs::write(&path, DOCUMENT).unwrap();
+        let clock = Arc::new(ManualClock::new(tuesday_at(12, 30)));
+        let mut manager = ReminderManager::with_file(path.to_str().unwrap(), clock.clone());
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Eat lunch");
+
+        // Acknowledging lasts until the period's end
+        assert!(manager.acknowledge("eat").unwrap());
+        clock.set(tuesday_at(13, 0));
+        assert!(manager.is_acknowledged("eat"));
+        clock.set(tuesday_at(13, 1));
+        assert!(!manager.is_acknowledged("eat"));
+
+        fs::write(&path, DOCUMENT.replace("12:00-13:00", "13:30-14:00")).unwrap();
+        assert!(manager.check_for_updates());
+        assert!(manager.get_current_reminder().is_none());
+    }
+}
+
+#[cfg(test)]
+mod date_bound_tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+    use reminder_display::clock::ManualClock;
+    use std::sync::Arc;
+
+    fn at(month: u32, day: u32, hour: u32) -> chrono::DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, month, day, hour, 0, 0)
+            .unwrap()
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_one_off_date() {
+        let reminder = Reminder {
+            text: "Release freeze starts".to_string(),
+            date: Some(date(11, 3)),
+            ..Default::default()
+        };
+
+        assert!(reminder.is_active_at(&at(11, 3, 9)));
+        assert!(!reminder.is_active_at(&at(11, 2, 9)));
+        assert!(!reminder.is_active_at(&at(11, 4, 9)));
+        assert!(reminder.is_active_at(&at(11, 3, 23)));
+        assert!(!reminder.is_active_at(&at(11, 4, 0)));
+    }
+
+    #[test]
+    fn test_start_and_end_dates_are_inclusive() {
+        let reminder = Reminder {
+            text: "Submit expense reports".to_string(),
+            start_date: Some(date(11, 1)),
+            end_date: Some(date(11, 15)),
+            ..Default::default()
+        };
+
+        assert!(!reminder.is_active_at(&at(10, 31, 12)));
+        assert!(reminder.is_active_at(&at(11, 1, 0)));
+        assert!(reminder.is_active_at(&at(11, 15, 23)));
+        assert!(!reminder.is_active_at(&at(11, 16, 0)));
+    }
+
+    #[test]
+    fn test_overnight_window_on_last_day_runs_past_midnight() {
+        let reminder = Reminder {
+            text: "Maintenance window".to_string(),
+            time_range: Some("22:00-02:00".parse().unwrap()),
+            date: Some(date(11, 3)),
+            ..Default::default()
+        };
+
+        assert!(reminder.is_active_at(&at(11, 4, 1)));
+        assert!(!reminder.is_active_at(&at(11, 3, 1)));
+    }
+
+    #[test]
+    fn test_dates_are_read_from_json_and_validated() {
+        let reminder: Reminder = serde_json::from_str(
+            r#"{"text": "Deadline", "category": "Planning", "priority": "high",
+                "time_range": null, "days": null, "start_date": "2026-11-15", "end_date": "2026-11-01"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(reminder.start_date, Some(date(11, 15)));
+        assert_eq!(reminder.validate().len(), 1);
+        assert!(
+            serde_json::from_str::<Reminder>(
+                r#"{"text": "Bad", "category": "", "priority": "low", "time_range": null, "days": null, "date": "2026-13-01"}"#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_manager_hides_expired_reminders() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("reminders.json");
+        fs::write(
+            &path,
+            r#"[
+  {"text": "Freeze", "category": "Release", "priority": "urgent", "time_range": null, "days": null, "end_date": "2026-11-10"},
+  {"text": "Always", "category": "Health", "priority": "low", "time_range": null, "days": null}
+]"#,
+        )
+        .unwrap();
+        let clock = ManualClock::new(at(11, 10, 9));
+        let manager = ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock.clone()));
+        assert_eq!(manager.get_total_reminders(), 2);
+
+        clock.set(at(11, 11, 9));
+        assert_eq!(manager.get_total_reminders(), 1);
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Always");
+    }
+}
+
+#[cfg(test)]
+mod id_tests {
+    use super::*;
+    use crate::common::manager_with;
+    use reminder_display::ReminderError;
+
+    fn reminder(id: Option<&str>, text: &str) -> Reminder {
+        Reminder {
+            id: id.map(|id| id.to_string()),
+            text: text.to_string(),
+            category: "Test".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_derived_id_is_stable_and_content_based() {
+        let a = reminder(None, "Stretch");
+        let b = reminder(None, "Stretch");
+        let c = reminder(None, "Hydrate");
+
+        assert_eq!(a.id(), b.id());
+        assert_ne!(a.id(), c.id());
+        assert_eq!(reminder(Some("stretch"), "Stretch").id(), "stretch");
+    }
+
+    #[test]
+    fn test_duplicate_and_empty_ids_are_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let (manager, _) = manager_with(
+            &temp_dir,
+            "reminders.json",
+            r#"[
+  {"id": "backup", "text": "Check backups", "category": "", "priority": "low", "time_range": null, "days": null},
+  {"id": "backup", "text": "Check backups again", "category": "", "priority": "low", "time_range": null, "days": null},
+  {"id": " ", "text": "Blank", "category": "", "priority": "low", "time_range": null, "days": null}
+]"#,
+        );
+
+        let issues = manager.last_error().unwrap().issues();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].index, Some(1));
+        assert!(issues[0].message.contains("reminder #1"));
+        assert_eq!(issues[1].index, Some(2));
+    }
+
+    #[test]
+    fn test_get_list_upsert_and_remove() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut manager, path) = manager_with(
+            &temp_dir,
+            "reminders.json",
+            r#"[
+  {"id": "backup", "text": "Check backups", "category": "Ops", "priority": "low", "time_range": null, "days": null},
+  {"text": "Stretch", "category": "Health", "priority": "low", "time_range": null, "days": null}
+]"#,
+        );
+        assert_eq!(manager.list().len(), 2);
+        assert_eq!(manager.get("backup").unwrap().text, "Check backups");
+        let stretch_id = manager.list()[1].id();
+        assert_eq!(manager.get(&stretch_id).unwrap().text, "Stretch");
+
+        manager
+            .upsert(reminder(Some("backup"), "Check backups and restores"))
+            .unwrap();
+        manager
+            .upsert(reminder(Some("water"), "Drink water"))
+            .unwrap();
+        assert_eq!(manager.list().len(), 3);
+        assert_eq!(manager.list()[0].text, "Check backups and restores");
+        assert_eq!(manager.list()[2].text, "Drink water");
+
+        assert_eq!(
+            manager.remove(&stretch_id).unwrap().unwrap().text,
+            "Stretch"
+        );
+        assert!(manager.remove("missing").unwrap().is_none());
+
+        // Changes are written to the file, and reloading it is a no-op
+        let saved: Vec<Reminder> =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved.len(), 2);
+        assert_eq!(saved[1].id.as_deref(), Some("water"));
+        assert!(!manager.check_for_updates());
+    }
+
+    #[test]
+    fn test_edits_keep_the_rest_of_the_file_as_written() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut manager, path) = manager_with(
+            &temp_dir,
+            "reminders.json",
+            r#"[
+    {"id": "backup",   "text": "Check backups", "category": "Ops", "priority": "low", "time_range": null, "days": null},
+    {
+        "id": "stretch",
+        "text": "Stretch",   "category": "Health", "priority": "low", "time_range": null, "days": null
+    },
+    {"id": "tea", "text": "Tea break", "category": "Health", "priority": "low", "time_range": null, "days": null}
+]
+"#,
+        );
+
+        manager
+            .upsert(reminder(Some("stretch"), "Stretch your legs"))
+            .unwrap();
+        manager
+            .upsert(reminder(Some("water"), "Drink water"))
+            .unwrap();
+        manager.remove("tea").unwrap();
+
+        // Untouched entries stay as they were; new ones follow their neighbours
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            r#"[
+    {"id": "backup",   "text": "Check backups", "category": "Ops", "priority": "low", "time_range": null, "days": null},
+    {
+        "id": "stretch",
+        "text": "Stretch your legs",
+        "category": "Test",
+        "priority": "medium"
+    },
+    {"id": "water", "text": "Drink water", "category": "Test", "priority": "medium"}
+]
+"#
+        );
+        assert!(!manager.check_for_updates());
+    }
+
+    #[test]
+    fn test_replace_moves_state_to_a_new_derived_id() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut manager, path) = manager_with(
+            &temp_dir,
+            "reminders.json",
+            r#"[{"text": "Stretch", "category": "Health", "priority": "low"}]"#,
+        );
+        let old_id = manager.list()[0].id();
+        assert!(manager.snooze(&old_id, 30).unwrap());
+
+        let mut stretch = manager.list()[0].clone();
+        stretch.text = "Stretch your legs".to_string();
+        assert!(manager.replace(&old_id, stretch).unwrap());
+
+        let new_id = manager.list()[0].id();
+        assert_ne!(new_id, old_id);
+        assert!(manager.is_snoozed(&new_id));
+        assert!(!manager.is_snoozed(&old_id));
+        assert!(!fs::read_to_string(&path).unwrap().contains("\"id\""));
+        assert!(!manager.replace("missing", reminder(None, "Other")).unwrap());
+    }
+
+    #[test]
+    fn test_upsert_rejects_invalid_reminder() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut manager, path) = manager_with(&temp_dir, "reminders.json", "[]");
+
+        let result = manager.upsert(reminder(Some("empty"), " "));
+        assert!(matches!(result, Err(ReminderError::Invalid { .. })));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_edits_are_refused_while_file_is_broken() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut manager, path) = manager_with(&temp_dir, "reminders.json", "[");
+
+        assert!(manager.upsert(reminder(Some("new"), "New")).is_err());
+        assert!(manager.remove("anything").is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[");
+    }
+}
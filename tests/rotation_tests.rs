@@ -0,0 +1,134 @@
+use chrono::{Duration, Local, TimeZone};
+use reminder_display::clock::ManualClock;
+use reminder_display::reminders::{Reminder, ReminderManager};
+use reminder_display::rotation::{
+    PinUrgent, PriorityWeighted, RotationKind, RotationStrategy, RoundRobin, Shuffle,
+};
+use reminder_display::schedule::Priority;
+use std::collections::HashSet;
+use std::fs;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn reminder(text: &str, priority: Priority) -> Reminder {
+    Reminder {
+        text: text.to_string(),
+        priority,
+        ..Default::default()
+    }
+}
+
+fn picks(strategy: &mut dyn RotationStrategy, reminders: &[Reminder], count: usize) -> Vec<usize> {
+    let active: Vec<&Reminder> = reminders.iter().collect();
+    let mut current = 0;
+    (0..count)
+        .map(|_| {
+            current = strategy.next(&active, current);
+            current
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod rotation_tests {
+    use super::*;
+
+    #[test]
+    fn test_round_robin_wraps() {
+        let reminders = vec![
+            reminder("a", Priority::Low),
+            reminder("b", Priority::Urgent),
+            reminder("c", Priority::Low),
+        ];
+        assert_eq!(picks(&mut RoundRobin, &reminders, 4), vec![1, 2, 0, 1]);
+    }
+
+    #[test]
+    fn test_priority_weighted_shares_turns_by_weight() {
+        let reminders = vec![
+            reminder("tip", Priority::Low),
+            reminder("alert", Priority::High),
+        ];
+        let shown = picks(&mut PriorityWeighted::default(), &reminders, 8);
+
+        assert_eq!(shown.iter().filter(|&&i| i == 1).count(), 6);
+        assert_eq!(shown.iter().filter(|&&i| i == 0).count(), 2);
+        // Turns are interleaved, not bunched up
+        assert!(shown.windows(4).all(|w| w.contains(&1)));
+    }
+
+    #[test]
+    fn test_shuffle_shows_each_reminder_once_per_round() {
+        let reminders: Vec<Reminder> = (0..5)
+            .map(|i| reminder(&i.to_string(), Priority::Medium))
+            .collect();
+        let shown = picks(&mut Shuffle::with_seed(7), &reminders, 50);
+
+        for round in shown.chunks(5) {
+            assert_eq!(round.iter().collect::<HashSet<_>>().len(), 5);
+        }
+        assert!(shown.windows(2).all(|w| w[0] != w[1]));
+    }
+
+    #[test]
+    fn test_pin_urgent_holds_urgent_reminder() {
+        let reminders = vec![
+            reminder("a", Priority::Low),
+            reminder("b", Priority::Medium),
+            reminder("fire", Priority::Urgent),
+        ];
+        assert_eq!(picks(&mut PinUrgent, &reminders, 3), vec![2, 2, 2]);
+
+        let calm = vec![
+            reminder("a", Priority::Low),
+            reminder("b", Priority::Medium),
+        ];
+        assert_eq!(picks(&mut PinUrgent, &calm, 3), vec![1, 0, 1]);
+    }
+
+    #[test]
+    fn test_rotation_kind_names() {
+        assert_eq!(
+            "round-robin".parse::<RotationKind>().unwrap(),
+            RotationKind::RoundRobin
+        );
+        assert_eq!(
+            "Priority_Weighted".parse::<RotationKind>().unwrap(),
+            RotationKind::PriorityWeighted
+        );
+        assert_eq!(
+            "shuffle".parse::<RotationKind>().unwrap(),
+            RotationKind::Shuffle
+        );
+        assert_eq!(
+            "pin-urgent".parse::<RotationKind>().unwrap(),
+            RotationKind::PinUrgent
+        );
+        assert!("random".parse::<RotationKind>().is_err());
+    }
+
+    #[test]
+    fn test_manager_uses_rotation_strategy() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("reminders.json");
+        fs::write(
+            &path,
+            r#"[
+                {"text": "Stretch", "category": "Health", "priority": "low", "time_range": null, "days": null},
+                {"text": "Server down", "category": "Ops", "priority": "urgent", "time_range": null, "days": null},
+                {"text": "Drink water", "category": "Health", "priority": "low", "time_range": null, "days": null}
+            ]"#,
+        )
+        .unwrap();
+        let clock = ManualClock::new(Local.with_ymd_and_hms(2026, 10, 16, 10, 0, 0).unwrap());
+        let mut manager =
+            ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock.clone()));
+        manager.set_rotation_strategy(RotationKind::PinUrgent.create());
+
+        for _ in 0..3 {
+            clock.advance(Duration::seconds(30));
+            manager.rotate_if_needed();
+            assert_eq!(manager.get_current_reminder().unwrap().text, "Server down");
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use chrono::{DateTime, Duration, Local, TimeZone, Utc};
+use reminder_display::clock::ManualClock;
+use reminder_display::reminders::{Reminder, ReminderManager};
+use reminder_display::schedule::ScheduleContext;
+use reminder_display::state::ReminderState;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+// Monday, 2 November 2026
+fn monday_at(hour: u32, minute: u32) -> DateTime<Local> {
+    Local
+        .with_ymd_and_hms(2026, 11, 2, hour, minute, 0)
+        .unwrap()
+}
+
+fn utc(local: DateTime<Local>) -> DateTime<Utc> {
+    local.with_timezone(&Utc)
+}
+
+fn parse(json: &str) -> Reminder {
+    serde_json::from_str(json).unwrap()
+}
+
+const REMINDERS: &str = r#"[
+  {"id": "backup", "text": "Check backups", "category": "Ops", "priority": "high", "time_range": "09:00-11:00", "days": null},
+  {"id": "stretch", "text": "Stretch", "category": "Health", "priority": "low", "time_range": null, "days": null}
+]"#;
+
+fn write_reminders(dir: &TempDir) -> PathBuf {
+    let path = dir.path().join("work_reminders.json");
+    fs::write(&path, REMINDERS).unwrap();
+    path
+}
+
+#[cfg(test)]
+mod state_tests {
+    use super::*;
+
+    #[test]
+    fn test_occurrence_end() {
+        let context = ScheduleContext::default();
+        let range = parse(
+            r#"{"text": "r", "category": "", "priority": "low", "time_range": "09:00-11:00", "days": null}"#,
+        );
+        let overnight = parse(
+            r#"{"text": "o", "category": "", "priority": "low", "time_range": "22:00-06:00", "days": null}"#,
+        );
+        let all_day = parse(
+            r#"{"text": "a", "category": "", "priority": "low", "time_range": null, "days": null}"#,
+        );
+        let cron = parse(
+            r#"{"text": "c", "category": "", "priority": "low", "time_range": null, "days": null, "cron": {"schedule": "0 9 * * *", "duration_mins": 30}}"#,
+        );
+
+        assert_eq!(
+            range.occurrence_end_in(&monday_at(10, 0), &context),
+            Some(utc(monday_at(11, 1)))
+        );
+        assert_eq!(range.occurrence_end_in(&monday_at(12, 0), &context), None);
+        // Halfway through the end minute it's still on, until the next one
+        let end_minute = monday_at(11, 0) + Duration::seconds(30);
+        assert!(range.is_active_in(&end_minute, &context));
+        assert_eq!(
+            range.occurrence_end_in(&end_minute, &context),
+            Some(utc(monday_at(11, 1)))
+        );
+        let after = monday_at(11, 1);
+        assert!(!range.is_active_in(&after, &context));
+        assert_eq!(range.occurrence_end_in(&after, &context), None);
+        let overnight_end = monday_at(6, 0) + Duration::seconds(59);
+        assert!(overnight.is_active_in(&overnight_end, &context));
+        assert_eq!(
+            overnight.occurrence_end_in(&overnight_end, &context),
+            Some(utc(monday_at(6, 1)))
+        );
+        assert_eq!(
+            overnight.occurrence_end_in(&monday_at(23, 0), &context),
+            Some(utc(monday_at(6, 1) + Duration::days(1)))
+        );
+        assert_eq!(
+            overnight.occurrence_end_in(&monday_at(5, 0), &context),
+            Some(utc(monday_at(6, 1)))
+        );
+        assert_eq!(
+            all_day.occurrence_end_in(&monday_at(15, 0), &context),
+            Some(utc(monday_at(0, 0) + Duration::days(1)))
+        );
+        assert_eq!(
+            cron.occurrence_end_in(&monday_at(9, 10), &context),
+            Some(utc(monday_at(9, 30)))
+        );
+    }
+
+    #[test]
+    fn test_state_path_sits_next_to_reminders_file() {
+        assert_eq!(
+            ReminderState::path_for(Path::new("/home/me/work_reminders.json")),
+            PathBuf::from("/home/me/work_reminders.state.json")
+        );
+    }
+
+    #[test]
+    fn test_acknowledge_hides_until_occurrence_ends() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_reminders(&temp_dir);
+        let clock = ManualClock::new(monday_at(9, 30));
+        let mut manager =
+            ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock.clone()));
+        assert_eq!(
+            manager.get_current_reminder().unwrap().text,
+            "Check backups"
+        );
+
+        assert!(manager.acknowledge_current().unwrap());
+        assert!(manager.is_acknowledged("backup"));
+        assert_eq!(manager.get_total_reminders(), 1);
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Stretch");
+
+        // Back for the next day's window
+        clock.set(monday_at(9, 30) + Duration::days(1));
+        assert!(!manager.is_acknowledged("backup"));
+        assert_eq!(manager.get_total_reminders(), 2);
+    }
+
+    #[test]
+    fn test_acknowledgements_survive_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_reminders(&temp_dir);
+        let clock = ManualClock::new(monday_at(9, 30));
+        let mut manager =
+            ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock.clone()));
+        assert!(manager.acknowledge("stretch").unwrap());
+        assert!(temp_dir.path().join("work_reminders.state.json").exists());
+        drop(manager);
+
+        let restarted = ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock.clone()));
+        assert!(restarted.is_acknowledged("stretch"));
+        assert_eq!(restarted.get_total_reminders(), 1);
+        // The reminders file itself is untouched
+        assert_eq!(fs::read_to_string(&path).unwrap(), REMINDERS);
+    }
+
+    #[test]
+    fn test_acknowledge_unknown_or_inactive_reminder() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_reminders(&temp_dir);
+        let clock = ManualClock::new(monday_at(14, 0));
+        let mut manager = ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock));
+
+        assert!(!manager.acknowledge("missing").unwrap());
+        assert!(!manager.acknowledge("backup").unwrap());
+        assert!(!temp_dir.path().join("work_reminders.state.json").exists());
+    }
+
+    #[test]
+    fn test_expired_entries_are_pruned() {
+        let mut state = ReminderState::default();
+        state.acknowledge("old", utc(monday_at(9, 0)));
+        state.acknowledge("new", utc(monday_at(18, 0)));
+        state.prune(utc(monday_at(12, 0)));
+
+        assert!(!state.acknowledged.contains_key("old"));
+        assert!(state.is_acknowledged("new", utc(monday_at(12, 0))));
+    }
+
+    #[test]
+    fn test_snooze_hides_for_the_given_minutes() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_reminders(&temp_dir);
+        let clock = ManualClock::new(monday_at(9, 30));
+        let mut manager =
+            ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock.clone()));
+        assert_eq!(
+            manager.get_current_reminder().unwrap().text,
+            "Check backups"
+        );
+
+        assert!(manager.snooze_current(15).unwrap());
+        assert!(manager.is_snoozed("backup"));
+        assert_eq!(manager.get_current_reminder().unwrap().text, "Stretch");
+
+        clock.advance(Duration::minutes(14));
+        assert_eq!(manager.get_total_reminders(), 1);
+        clock.advance(Duration::minutes(1));
+        assert!(!manager.is_snoozed("backup"));
+        assert_eq!(manager.get_total_reminders(), 2);
+    }
+
+    #[test]
+    fn test_snoozes_survive_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_reminders(&temp_dir);
+        let clock = ManualClock::new(monday_at(9, 30));
+        let mut manager =
+            ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock.clone()));
+        assert!(manager.snooze("stretch", 60).unwrap());
+        assert!(!manager.snooze("missing", 60).unwrap());
+        drop(manager);
+
+        let state = ReminderState::load(&ReminderState::path_for(&path)).unwrap();
+        assert_eq!(state.snoozed["stretch"], utc(monday_at(10, 30)));
+
+        let restarted = ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock.clone()));
+        assert!(restarted.is_snoozed("stretch"));
+        assert_eq!(
+            restarted.get_current_reminder().unwrap().text,
+            "Check backups"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use chrono::{TimeZone, Utc};
+use chrono_tz::Tz;
+use reminder_display::clock::ManualClock;
+use reminder_display::reminders::{Reminder, ReminderManager};
+use reminder_display::schedule::ScheduleContext;
+use std::fs;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> chrono::DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+        .unwrap()
+}
+
+fn parse(json: &str) -> Reminder {
+    serde_json::from_str(json).unwrap()
+}
+
+#[cfg(test)]
+mod timezone_tests {
+    use super::*;
+
+    #[test]
+    fn test_window_is_evaluated_in_reminder_zone() {
+        let standup = parse(
+            r#"{"text": "EU standup starts", "category": "Meetings", "priority": "high",
+                "time_range": "09:00-09:30", "days": ["monday"], "timezone": "Europe/Berlin"}"#,
+        );
+
+        // Berlin is UTC+1 in November
+        assert!(standup.is_active_at(&utc(2026, 11, 2, 8, 15)));
+        assert!(!standup.is_active_at(&utc(2026, 11, 2, 9, 15)));
+    }
+
+    #[test]
+    fn test_dst_transition_shifts_utc_window() {
+        let standup = parse(
+            r#"{"text": "EU standup starts", "category": "Meetings", "priority": "high",
+                "time_range": "09:00-09:30", "days": null, "timezone": "Europe/Berlin"}"#,
+        );
+
+        // Berlin moves to UTC+2 on 29 March 2026
+        assert!(standup.is_active_at(&utc(2026, 3, 27, 8, 15)));
+        assert!(!standup.is_active_at(&utc(2026, 3, 30, 8, 15)));
+        assert!(standup.is_active_at(&utc(2026, 3, 30, 7, 15)));
+    }
+
+    #[test]
+    fn test_day_filter_uses_reminder_zone_date() {
+        let tokyo = parse(
+            r#"{"text": "Tokyo Monday", "category": "Test", "priority": "low",
+                "time_range": null, "days": ["monday"], "timezone": "Asia/Tokyo"}"#,
+        );
+
+        // Sunday 20:00 UTC is Monday 05:00 in Tokyo
+        assert!(tokyo.is_active_at(&utc(2026, 11, 1, 20, 0)));
+        assert!(!tokyo.is_active_at(&utc(2026, 11, 2, 20, 0)));
+    }
+
+    #[test]
+    fn test_cron_fires_on_local_wall_clock_across_dst() {
+        let reminder = parse(
+            r#"{"text": "Daily check", "category": "Test", "priority": "low",
+                "time_range": null, "days": null, "timezone": "America/New_York",
+                "cron": {"schedule": "0 9 * * *", "duration_mins": 30}}"#,
+        );
+
+        // New York is UTC-4 before 1 November 2026 and UTC-5 after
+        assert!(reminder.is_active_at(&utc(2026, 10, 31, 13, 10)));
+        assert!(reminder.is_active_at(&utc(2026, 11, 2, 14, 10)));
+        assert!(!reminder.is_active_at(&utc(2026, 11, 2, 13, 10)));
+    }
+
+    #[test]
+    fn test_default_timezone_applies_to_reminders_without_one() {
+        let plain = parse(
+            r#"{"text": "Plain", "category": "Test", "priority": "low",
+                "time_range": "09:00-10:00", "days": null}"#,
+        );
+        let context = ScheduleContext {
+            default_timezone: Some(Tz::Europe__Berlin),
+            ..Default::default()
+        };
+
+        assert!(plain.is_active_in(&utc(2026, 11, 2, 8, 30), &context));
+        assert!(!plain.is_active_in(&utc(2026, 11, 2, 9, 30), &context));
+        assert!(plain.is_active_at(&utc(2026, 11, 2, 9, 30)));
+    }
+
+    #[test]
+    fn test_unknown_zone_is_rejected() {
+        let result = serde_json::from_str::<Reminder>(
+            r#"{"text": "Bad", "category": "Test", "priority": "low",
+                "time_range": null, "days": null, "timezone": "Europe/Atlantis"}"#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_manager_default_timezone() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("reminders.json");
+        fs::write(
+            &path,
+            r#"[{"text": "Berlin morning", "category": "Test", "priority": "low",
+                 "time_range": "09:00-10:00", "days": null}]"#,
+        )
+        .unwrap();
+        let clock = ManualClock::new(utc(2026, 11, 2, 8, 30).into());
+        let mut manager = ReminderManager::with_file(path.to_str().unwrap(), Arc::new(clock));
+
+        manager.set_default_timezone(Some(Tz::Europe__Berlin));
+        assert_eq!(manager.get_total_reminders(), 1);
+        manager.set_default_timezone(Some(Tz::Asia__Tokyo));
+        assert_eq!(manager.get_total_reminders(), 0);
+    }
+}
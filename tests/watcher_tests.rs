@@ -0,0 +1,131 @@
+use reminder_display::{ReminderManager, ReminderWatcher};
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+const REMINDER_JSON: &str = r#"[
+  {
+    "text": "Watched",
+    "category": "Test",
+    "priority": "high",
+    "time_range": null,
+    "days": null
+  }
+]"#;
+
+fn wait_for(condition: impl Fn() -> bool) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    false
+}
+
+#[cfg(test)]
+mod watcher_tests {
+    use super::*;
+
+    #[test]
+    fn test_watcher_reloads_on_write_and_atomic_rename() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("watched.json");
+        fs::write(&file_path, "[]").unwrap();
+
+        let manager = Arc::new(Mutex::new(ReminderManager::with_file(
+            file_path.to_str().unwrap(),
+            Arc::new(reminder_display::SystemClock),
+        )));
+        assert_eq!(manager.lock().unwrap().get_total_reminders(), 0);
+
+        let reloads = Arc::new(AtomicUsize::new(0));
+        let reloads_clone = reloads.clone();
+        let _watcher = ReminderWatcher::spawn(manager.clone(), move || {
+            reloads_clone.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+
+        // Plain in-place write
+        fs::write(&file_path, REMINDER_JSON).unwrap();
+        assert!(wait_for(
+            || manager.lock().unwrap().get_total_reminders() == 1
+        ));
+
+        // Editor-style save: write a temp file and rename it over the original
+        let temp_file = temp_dir.path().join(".watched.json.swp");
+        fs::write(&temp_file, "[]").unwrap();
+        fs::rename(&temp_file, &file_path).unwrap();
+        assert!(wait_for(
+            || manager.lock().unwrap().get_total_reminders() == 0
+        ));
+
+        // on_reload runs just after the manager is unlocked
+        assert!(wait_for(|| reloads.load(Ordering::SeqCst) >= 2));
+    }
+
+    #[test]
+    fn test_watcher_picks_up_files_added_to_a_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.json"), "[]").unwrap();
+        let manager = Arc::new(Mutex::new(ReminderManager::with_file(
+            temp_dir.path().to_str().unwrap(),
+            Arc::new(reminder_display::SystemClock),
+        )));
+        assert_eq!(manager.lock().unwrap().list().len(), 0);
+
+        let _watcher = ReminderWatcher::spawn(manager.clone(), || {}).unwrap();
+
+        fs::write(temp_dir.path().join("b.json"), REMINDER_JSON).unwrap();
+        assert!(wait_for(|| manager.lock().unwrap().list().len() == 1));
+
+        // Files that aren't read don't trigger a reload
+        fs::write(temp_dir.path().join("notes.txt"), "x").unwrap();
+        fs::remove_file(temp_dir.path().join("b.json")).unwrap();
+        assert!(wait_for(|| manager.lock().unwrap().list().is_empty()));
+    }
+
+    #[test]
+    fn test_watcher_follows_included_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let shared = temp_dir.path().join("shared");
+        fs::create_dir(&shared).unwrap();
+        fs::write(shared.join("company.json"), "[]").unwrap();
+        let desk = temp_dir.path().join("desk");
+        fs::create_dir(&desk).unwrap();
+        fs::write(
+            desk.join("reminders.json"),
+            r#"{"version": 2, "include": ["../shared/company.json"]}"#,
+        )
+        .unwrap();
+        let manager = Arc::new(Mutex::new(ReminderManager::with_file(
+            desk.join("reminders.json").to_str().unwrap(),
+            Arc::new(reminder_display::SystemClock),
+        )));
+
+        let _watcher = ReminderWatcher::spawn(manager.clone(), || {}).unwrap();
+
+        fs::write(shared.join("company.json"), REMINDER_JSON).unwrap();
+        assert!(wait_for(|| manager.lock().unwrap().list().len() == 1));
+    }
+
+    #[test]
+    fn test_watcher_reports_a_file_renamed_away() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("watched.json");
+        fs::write(&file_path, REMINDER_JSON).unwrap();
+        let manager = Arc::new(Mutex::new(ReminderManager::with_file(
+            file_path.to_str().unwrap(),
+            Arc::new(reminder_display::SystemClock),
+        )));
+        let _watcher = ReminderWatcher::spawn(manager.clone(), || {}).unwrap();
+
+        fs::rename(&file_path, temp_dir.path().join("watched.json.bak")).unwrap();
+        assert!(wait_for(|| manager.lock().unwrap().last_error().is_some()));
+        assert!(!file_path.exists());
+        assert_eq!(manager.lock().unwrap().list().len(), 1);
+    }
+}